@@ -2,7 +2,7 @@
 
 extern crate test;
 
-use calamine::{open_workbook, Ods, Reader, Xls, Xlsb, Xlsx};
+use calamine::{open_workbook, DataType, Ods, Reader, Xls, Xlsb, Xlsx};
 use std::fs::File;
 use std::io::BufReader;
 use test::Bencher;
@@ -81,3 +81,41 @@ fn bench_xlsb_cells_reader(b: &mut Bencher) {
     }
     b.iter(|| count::<Xlsx<_>>("tests/issues.xlsb"));
 }
+
+#[bench]
+fn bench_xlsx_range_data_sum(b: &mut Bencher) {
+    let path = format!("{}/tests/1000_rows.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut excel: Xlsx<_> = open_workbook(&path).expect("cannot open excel file");
+    b.iter(|| {
+        let range = excel.worksheet_range("Sheet1").unwrap();
+        range
+            .rows()
+            .flat_map(|r| r.iter())
+            .filter_map(|c| c.get_float().or_else(|| c.get_int().map(|i| i as f64)))
+            .sum::<f64>()
+    });
+}
+
+#[bench]
+fn bench_xlsx_range_f64_sum(b: &mut Bencher) {
+    let path = format!("{}/tests/1000_rows.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut excel: Xlsx<_> = open_workbook(&path).expect("cannot open excel file");
+    b.iter(|| {
+        let range = excel.worksheet_range_f64("Sheet1").unwrap();
+        range.rows().flatten().filter(|v| !v.is_nan()).sum::<f64>()
+    });
+}
+
+#[bench]
+fn bench_xlsx_merge_cells(b: &mut Bencher) {
+    let path = format!("{}/tests/many_merge_cells.xlsx", env!("CARGO_MANIFEST_DIR"));
+    b.iter(|| {
+        // merged regions are cached with the sheet, so each iteration needs a new reader
+        let mut excel: Xlsx<_> = open_workbook(&path).expect("cannot open excel file");
+        excel
+            .worksheet_merge_cells("Sheet1")
+            .unwrap()
+            .unwrap()
+            .len()
+    });
+}
@@ -8,7 +8,7 @@ use crate::{
 };
 use std::borrow::Cow;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Cursor};
 use std::path::Path;
 
 /// A wrapper over all sheets when the file type is not known at static time
@@ -21,37 +21,73 @@ pub enum Sheets<RS> {
     Xlsb(Xlsb<RS>),
     /// Ods reader
     Ods(Ods<RS>),
+    /// Csv reader
+    #[cfg(feature = "csv")]
+    Csv(crate::Csv<RS>),
 }
 
 /// Opens a workbook and define the file type at runtime.
 ///
+/// The file type is guessed from the extension. If the matching reader fails (e.g. an
+/// `.xlt` template saved in the xlsx format), or for unknown extensions, the file type
+/// is detected from the content.
+///
 /// Whenever possible use the statically known `open_workbook` function instead
 pub fn open_workbook_auto<P>(path: P) -> Result<Sheets<BufReader<File>>, Error>
 where
     P: AsRef<Path>,
 {
     let path = path.as_ref();
-    Ok(match path.extension().and_then(|e| e.to_str()) {
-        Some("xls") | Some("xla") => Sheets::Xls(open_workbook(path).map_err(Error::Xls)?),
-        Some("xlsx") | Some("xlsm") | Some("xlam") => {
-            Sheets::Xlsx(open_workbook(path).map_err(Error::Xlsx)?)
+    let by_extension = match path.extension().and_then(|e| e.to_str()) {
+        Some("xls") | Some("xla") | Some("xlt") => {
+            open_workbook(path).map(Sheets::Xls).map_err(Error::Xls)
         }
-        Some("xlsb") => Sheets::Xlsb(open_workbook(path).map_err(Error::Xlsb)?),
-        Some("ods") => Sheets::Ods(open_workbook(path).map_err(Error::Ods)?),
-        _ => {
-            if let Ok(ret) = open_workbook::<Xls<_>, _>(path) {
-                return Ok(Sheets::Xls(ret));
-            } else if let Ok(ret) = open_workbook::<Xlsx<_>, _>(path) {
-                return Ok(Sheets::Xlsx(ret));
-            } else if let Ok(ret) = open_workbook::<Xlsb<_>, _>(path) {
-                return Ok(Sheets::Xlsb(ret));
-            } else if let Ok(ret) = open_workbook::<Ods<_>, _>(path) {
-                return Ok(Sheets::Ods(ret));
-            } else {
-                return Err(Error::Msg("Cannot detect file format"));
-            };
+        Some("xlsx") | Some("xlsm") | Some("xlam") | Some("xltx") | Some("xltm") => {
+            open_workbook(path).map(Sheets::Xlsx).map_err(Error::Xlsx)
         }
-    })
+        Some("xlsb") => open_workbook(path).map(Sheets::Xlsb).map_err(Error::Xlsb),
+        Some("ods") | Some("fods") => open_workbook(path).map(Sheets::Ods).map_err(Error::Ods),
+        #[cfg(feature = "csv")]
+        Some("csv") => open_workbook(path).map(Sheets::Csv).map_err(Error::Csv),
+        _ => Err(Error::Msg("Cannot detect file format")),
+    };
+    let err = match by_extension {
+        Ok(sheets) => return Ok(sheets),
+        Err(e) => e,
+    };
+    if let Ok(ret) = open_workbook::<Xls<_>, _>(path) {
+        Ok(Sheets::Xls(ret))
+    } else if let Ok(ret) = open_workbook::<Xlsx<_>, _>(path) {
+        Ok(Sheets::Xlsx(ret))
+    } else if let Ok(ret) = open_workbook::<Xlsb<_>, _>(path) {
+        Ok(Sheets::Xlsb(ret))
+    } else if let Ok(ret) = open_workbook::<Ods<_>, _>(path) {
+        Ok(Sheets::Ods(ret))
+    } else {
+        Err(err)
+    }
+}
+
+/// Opens a workbook from a fully buffered byte slice and define the file type at runtime.
+///
+/// Workbooks need a `Seek` reader so they must be buffered first. This is the intended
+/// pattern for async code: read the whole content (e.g. with `read_to_end`) then hand
+/// the buffer over. Parsing itself is synchronous.
+///
+/// Whenever possible use the statically known [`open_workbook_from_slice`](crate::open_workbook_from_slice)
+/// function instead
+///
+/// # Examples
+/// ```
+/// use calamine::{open_workbook_auto_from_bytes, Reader};
+///
+/// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+/// let bytes = std::fs::read(path).unwrap();
+/// let mut workbook = open_workbook_auto_from_bytes(&bytes).unwrap();
+/// assert!(workbook.worksheet_range("issue2").is_ok());
+/// ```
+pub fn open_workbook_auto_from_bytes(bytes: &[u8]) -> Result<Sheets<Cursor<&[u8]>>, Error> {
+    open_workbook_auto_from_rs(Cursor::new(bytes))
 }
 
 /// Opens a workbook from the given bytes.
@@ -99,6 +135,10 @@ where
             Sheets::Ods(ref mut e) => {
                 e.with_header_row(header_row);
             }
+            #[cfg(feature = "csv")]
+            Sheets::Csv(ref mut e) => {
+                e.with_header_row(header_row);
+            }
         }
         self
     }
@@ -110,6 +150,8 @@ where
             Sheets::Xlsx(ref mut e) => e.vba_project().map(|vba| vba.map_err(Error::Xlsx)),
             Sheets::Xlsb(ref mut e) => e.vba_project().map(|vba| vba.map_err(Error::Xlsb)),
             Sheets::Ods(ref mut e) => e.vba_project().map(|vba| vba.map_err(Error::Ods)),
+            #[cfg(feature = "csv")]
+            Sheets::Csv(ref mut e) => e.vba_project().map(|vba| vba.map_err(Error::Csv)),
         }
     }
 
@@ -120,6 +162,8 @@ where
             Sheets::Xlsx(ref e) => e.metadata(),
             Sheets::Xlsb(ref e) => e.metadata(),
             Sheets::Ods(ref e) => e.metadata(),
+            #[cfg(feature = "csv")]
+            Sheets::Csv(ref e) => e.metadata(),
         }
     }
 
@@ -130,6 +174,8 @@ where
             Sheets::Xlsx(ref mut e) => e.worksheet_range(name).map_err(Error::Xlsx),
             Sheets::Xlsb(ref mut e) => e.worksheet_range(name).map_err(Error::Xlsb),
             Sheets::Ods(ref mut e) => e.worksheet_range(name).map_err(Error::Ods),
+            #[cfg(feature = "csv")]
+            Sheets::Csv(ref mut e) => e.worksheet_range(name).map_err(Error::Csv),
         }
     }
 
@@ -140,6 +186,8 @@ where
             Sheets::Xlsx(ref mut e) => e.worksheet_formula(name).map_err(Error::Xlsx),
             Sheets::Xlsb(ref mut e) => e.worksheet_formula(name).map_err(Error::Xlsb),
             Sheets::Ods(ref mut e) => e.worksheet_formula(name).map_err(Error::Ods),
+            #[cfg(feature = "csv")]
+            Sheets::Csv(ref mut e) => e.worksheet_formula(name).map_err(Error::Csv),
         }
     }
 
@@ -149,6 +197,8 @@ where
             Sheets::Xlsx(ref mut e) => e.worksheets(),
             Sheets::Xlsb(ref mut e) => e.worksheets(),
             Sheets::Ods(ref mut e) => e.worksheets(),
+            #[cfg(feature = "csv")]
+            Sheets::Csv(ref mut e) => e.worksheets(),
         }
     }
 
@@ -159,6 +209,8 @@ where
             Sheets::Xlsx(ref e) => e.pictures(),
             Sheets::Xlsb(ref e) => e.pictures(),
             Sheets::Ods(ref e) => e.pictures(),
+            #[cfg(feature = "csv")]
+            Sheets::Csv(ref e) => e.pictures(),
         }
     }
 }
@@ -174,8 +226,16 @@ where
         match self {
             Sheets::Xlsx(ref mut e) => e.worksheet_range_ref(name).map_err(Error::Xlsx),
             Sheets::Xlsb(ref mut e) => e.worksheet_range_ref(name).map_err(Error::Xlsb),
-            Sheets::Xls(_) => unimplemented!(),
-            Sheets::Ods(_) => unimplemented!(),
+            Sheets::Xls(_) => Err(Error::Msg(
+                "worksheet_range_ref is not supported for xls files",
+            )),
+            Sheets::Ods(_) => Err(Error::Msg(
+                "worksheet_range_ref is not supported for ods files",
+            )),
+            #[cfg(feature = "csv")]
+            Sheets::Csv(_) => Err(Error::Msg(
+                "worksheet_range_ref is not supported for csv files",
+            )),
         }
     }
 }
@@ -61,6 +61,32 @@ impl std::error::Error for CfbError {
     }
 }
 
+impl PartialEq for CfbError {
+    fn eq(&self, other: &Self) -> bool {
+        use CfbError::*;
+        match (self, other) {
+            (Io(a), Io(b)) => io_error_eq(a, b),
+            (Ole, Ole) => true,
+            (EmptyRootDir, EmptyRootDir) => true,
+            (StreamNotFound(a), StreamNotFound(b)) => a == b,
+            (
+                Invalid {
+                    name: n1,
+                    expected: e1,
+                    found: f1,
+                },
+                Invalid {
+                    name: n2,
+                    expected: e2,
+                    found: f2,
+                },
+            ) => n1 == n2 && e1 == e2 && f1 == f2,
+            (CodePageNotFound(a), CodePageNotFound(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 /// A struct for managing Compound File Binary format
 #[derive(Debug, Clone)]
 pub struct Cfb {
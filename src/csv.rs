@@ -0,0 +1,224 @@
+//! A module to read csv files as single sheet workbooks
+//!
+//! Available with the `csv` feature.
+
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::utils::io_error_eq;
+use crate::vba::VbaProject;
+use crate::{Data, HeaderRow, Metadata, Range, Reader, Sheet, SheetType, SheetVisible};
+
+/// Name of the only sheet of a csv file
+pub const CSV_SHEET_NAME: &str = "Sheet1";
+
+/// An enum for csv specific errors
+#[derive(Debug)]
+pub enum CsvError {
+    /// Io error
+    Io(std::io::Error),
+    /// Csv error
+    Csv(::csv::Error),
+    /// Worksheet not found
+    WorksheetNotFound(String),
+}
+
+from_err!(std::io::Error, CsvError, Io);
+from_err!(::csv::Error, CsvError, Csv);
+
+impl std::fmt::Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "I/O error: {e}"),
+            CsvError::Csv(e) => write!(f, "Csv error: {e}"),
+            CsvError::WorksheetNotFound(name) => write!(f, "Worksheet '{name}' not found"),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Io(e) => Some(e),
+            CsvError::Csv(e) => Some(e),
+            CsvError::WorksheetNotFound(_) => None,
+        }
+    }
+}
+
+impl PartialEq for CsvError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (CsvError::Io(a), CsvError::Io(b)) => io_error_eq(a, b),
+            (CsvError::Csv(a), CsvError::Csv(b)) => a.to_string() == b.to_string(),
+            (CsvError::WorksheetNotFound(a), CsvError::WorksheetNotFound(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Csv reader options
+#[derive(Debug)]
+struct CsvOptions {
+    header_row: HeaderRow,
+    delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            header_row: HeaderRow::default(),
+            delimiter: b',',
+        }
+    }
+}
+
+/// A csv file reader, exposing the file as a single sheet named [`CSV_SHEET_NAME`]
+///
+/// Cell types are inferred from their text: integers, floats, booleans (`true` or
+/// `false`, case insensitive) and strings, empty fields being `Data::Empty`.
+/// Rows may have different lengths.
+///
+/// # Examples
+/// ```
+/// use calamine::{open_workbook_from_slice, Csv, Data, Reader};
+///
+/// let mut csv: Csv<_> = open_workbook_from_slice(b"name,value\na,1.5\n").unwrap();
+/// let range = csv.worksheet_range("Sheet1").unwrap();
+/// assert_eq!(range.get_value((1, 1)), Some(&Data::Float(1.5)));
+/// ```
+pub struct Csv<RS> {
+    reader: RS,
+    metadata: Metadata,
+    options: CsvOptions,
+}
+
+impl<RS> Csv<RS> {
+    /// Set the field delimiter, `b','` by default
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook_from_slice, Csv, Data, Reader};
+    ///
+    /// let mut csv: Csv<_> = open_workbook_from_slice(b"a;1\n").unwrap();
+    /// let range = csv.with_delimiter(b';').worksheet_range("Sheet1").unwrap();
+    /// assert_eq!(range.get_value((0, 1)), Some(&Data::Int(1)));
+    /// ```
+    pub fn with_delimiter(&mut self, delimiter: u8) -> &mut Self {
+        self.options.delimiter = delimiter;
+        self
+    }
+}
+
+impl<RS: Read + Seek> Csv<RS> {
+    fn read_range(&mut self) -> Result<Range<Data>, CsvError> {
+        self.reader.seek(SeekFrom::Start(0))?;
+        let mut reader = ::csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(self.options.delimiter)
+            .from_reader(&mut self.reader);
+        let mut cells = Vec::new();
+        let mut record = ::csv::StringRecord::new();
+        let mut row = 0;
+        while reader.read_record(&mut record)? {
+            for (col, field) in record.iter().enumerate() {
+                let value = parse_field(field);
+                if value != Data::Empty {
+                    cells.push(crate::Cell::new((row, col as u32), value));
+                }
+            }
+            row += 1;
+        }
+        Ok(Range::from_sparse(cells))
+    }
+}
+
+impl<RS> Reader<RS> for Csv<RS>
+where
+    RS: Read + Seek,
+{
+    type Error = CsvError;
+
+    fn new(reader: RS) -> Result<Self, CsvError> {
+        Ok(Csv {
+            reader,
+            metadata: Metadata {
+                sheets: vec![Sheet {
+                    name: CSV_SHEET_NAME.to_string(),
+                    typ: SheetType::WorkSheet,
+                    visible: SheetVisible::Visible,
+                }],
+                names: Vec::new(),
+            },
+            options: CsvOptions::default(),
+        })
+    }
+
+    fn with_header_row(&mut self, header_row: HeaderRow) -> &mut Self {
+        self.options.header_row = header_row;
+        self
+    }
+
+    fn vba_project(&mut self) -> Option<Result<Cow<'_, VbaProject>, CsvError>> {
+        None
+    }
+
+    fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    fn worksheet_range(&mut self, name: &str) -> Result<Range<Data>, CsvError> {
+        if name != CSV_SHEET_NAME {
+            return Err(CsvError::WorksheetNotFound(name.into()));
+        }
+        let range = self.read_range()?;
+        match self.options.header_row {
+            HeaderRow::FirstNonEmptyRow => Ok(range),
+            HeaderRow::Row(header_row_idx) => {
+                // If `header_row` is a row index, adjust the range
+                if let (Some(start), Some(end)) = (range.start(), range.end()) {
+                    Ok(range.range((header_row_idx, start.1), end))
+                } else {
+                    Ok(range)
+                }
+            }
+        }
+    }
+
+    fn worksheets(&mut self) -> Vec<(String, Range<Data>)> {
+        self.worksheet_range(CSV_SHEET_NAME)
+            .map(|range| vec![(CSV_SHEET_NAME.to_string(), range)])
+            .unwrap_or_default()
+    }
+
+    /// Csv files have no formula, an empty range is returned
+    fn worksheet_formula(&mut self, name: &str) -> Result<Range<String>, CsvError> {
+        if name != CSV_SHEET_NAME {
+            return Err(CsvError::WorksheetNotFound(name.into()));
+        }
+        Ok(Range::default())
+    }
+
+    #[cfg(feature = "picture")]
+    fn pictures(&self) -> Option<Vec<(String, Vec<u8>)>> {
+        None
+    }
+}
+
+/// Infers the type of a csv field
+fn parse_field(field: &str) -> Data {
+    if field.is_empty() {
+        Data::Empty
+    } else if let Ok(i) = field.parse() {
+        Data::Int(i)
+    } else if let Some(f) = field.parse::<f64>().ok().filter(|f| f.is_finite()) {
+        Data::Float(f)
+    } else if field.eq_ignore_ascii_case("true") {
+        Data::Bool(true)
+    } else if field.eq_ignore_ascii_case("false") {
+        Data::Bool(false)
+    } else {
+        Data::String(field.to_string())
+    }
+}
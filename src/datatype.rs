@@ -42,8 +42,37 @@ pub enum Data {
     Empty,
 }
 
-/// An enum to represent all different data types that can appear as
-/// a value in a worksheet cell
+impl Data {
+    /// Get the kind of the value, without its content
+    ///
+    /// ISO 8601 datetimes and durations are classified like their `ExcelDateTime`
+    /// counterparts.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Data, DataKind};
+    ///
+    /// assert_eq!(Data::Float(1.5).kind(), DataKind::Float);
+    /// assert_eq!(Data::DurationIso("PT1H".to_string()).kind(), DataKind::Duration);
+    /// ```
+    pub fn kind(&self) -> DataKind {
+        match self {
+            Data::Int(_) => DataKind::Int,
+            Data::Float(_) => DataKind::Float,
+            Data::String(_) => DataKind::String,
+            Data::Bool(_) => DataKind::Bool,
+            Data::DateTime(ExcelDateTime {
+                datetime_type: ExcelDateTimeType::TimeDelta,
+                ..
+            }) => DataKind::Duration,
+            Data::DateTime(_) | Data::DateTimeIso(_) => DataKind::DateTime,
+            Data::DurationIso(_) => DataKind::Duration,
+            Data::Error(_) => DataKind::Error,
+            Data::Empty => DataKind::Empty,
+        }
+    }
+}
+
 impl DataType for Data {
     fn is_empty(&self) -> bool {
         *self == Data::Empty
@@ -327,6 +356,27 @@ where
     }
 }
 
+/// The kind of a [`Data`] or [`DataRef`] value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    /// Signed integer
+    Int,
+    /// Float
+    Float,
+    /// String
+    String,
+    /// Boolean
+    Bool,
+    /// Date, Time or DateTime
+    DateTime,
+    /// Duration
+    Duration,
+    /// Error
+    Error,
+    /// Empty cell
+    Empty,
+}
+
 /// An enum to represent all different data types that can appear as
 /// a value in a worksheet cell
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -354,6 +404,28 @@ pub enum DataRef<'a> {
     Empty,
 }
 
+impl DataRef<'_> {
+    /// Get the kind of the value, without its content
+    ///
+    /// Shared strings are classified as `DataKind::String`.
+    pub fn kind(&self) -> DataKind {
+        match self {
+            DataRef::Int(_) => DataKind::Int,
+            DataRef::Float(_) => DataKind::Float,
+            DataRef::String(_) | DataRef::SharedString(_) => DataKind::String,
+            DataRef::Bool(_) => DataKind::Bool,
+            DataRef::DateTime(ExcelDateTime {
+                datetime_type: ExcelDateTimeType::TimeDelta,
+                ..
+            }) => DataKind::Duration,
+            DataRef::DateTime(_) | DataRef::DateTimeIso(_) => DataKind::DateTime,
+            DataRef::DurationIso(_) => DataKind::Duration,
+            DataRef::Error(_) => DataKind::Error,
+            DataRef::Empty => DataKind::Empty,
+        }
+    }
+}
+
 impl DataType for DataRef<'_> {
     fn is_empty(&self) -> bool {
         *self == DataRef::Empty
@@ -520,6 +592,23 @@ impl PartialEq<i64> for DataRef<'_> {
     }
 }
 
+impl fmt::Display for DataRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), fmt::Error> {
+        match *self {
+            DataRef::Int(ref e) => write!(f, "{}", e),
+            DataRef::Float(ref e) => write!(f, "{}", e),
+            DataRef::String(ref e) => write!(f, "{}", e),
+            DataRef::SharedString(e) => write!(f, "{}", e),
+            DataRef::Bool(ref e) => write!(f, "{}", e),
+            DataRef::DateTime(ref e) => write!(f, "{}", e),
+            DataRef::DateTimeIso(ref e) => write!(f, "{}", e),
+            DataRef::DurationIso(ref e) => write!(f, "{}", e),
+            DataRef::Error(ref e) => write!(f, "{}", e),
+            DataRef::Empty => Ok(()),
+        }
+    }
+}
+
 /// A trait to represent all different data types that can appear as
 /// a value in a worksheet cell
 pub trait DataType {
@@ -623,18 +712,10 @@ pub trait DataType {
     /// Try converting data type into a duration
     #[cfg(feature = "dates")]
     fn as_duration(&self) -> Option<chrono::Duration> {
-        use chrono::Timelike;
-
         if self.is_datetime() {
             self.get_datetime().and_then(|dt| dt.as_duration())
         } else if self.is_duration_iso() {
-            // need replace in the future to smth like chrono::Duration::from_str()
-            // https://github.com/chronotope/chrono/issues/579
-            self.as_time().map(|t| {
-                chrono::Duration::nanoseconds(
-                    t.num_seconds_from_midnight() as i64 * 1_000_000_000 + t.nanosecond() as i64,
-                )
-            })
+            self.get_duration_iso().and_then(parse_duration_iso)
         } else {
             None
         }
@@ -658,6 +739,40 @@ pub trait DataType {
         }
         .flatten()
     }
+
+    /// Convert data type into a json value
+    ///
+    /// Numbers and booleans are kept as is, datetimes become ISO8601 strings,
+    /// errors become `{"error": "#DIV/0!"}` like objects and empty cells become `null`.
+    #[cfg(feature = "json")]
+    fn as_json_value(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        if let Some(v) = self.get_int() {
+            Value::from(v)
+        } else if let Some(v) = self.get_float() {
+            serde_json::Number::from_f64(v).map_or(Value::Null, Value::Number)
+        } else if let Some(v) = self.get_bool() {
+            Value::Bool(v)
+        } else if let Some(v) = self.get_string() {
+            Value::String(v.to_string())
+        } else if let Some(v) = self.get_datetime() {
+            if v.is_duration() {
+                v.as_duration()
+                    .map_or(Value::Null, |d| Value::String(d.to_string()))
+            } else {
+                // `Debug` is the ISO8601 representation
+                v.as_datetime()
+                    .map_or(Value::Null, |d| Value::String(format!("{d:?}")))
+            }
+        } else if let Some(v) = self.get_datetime_iso().or_else(|| self.get_duration_iso()) {
+            Value::String(v.to_string())
+        } else if let Some(e) = self.get_error() {
+            serde_json::json!({ "error": e.to_string() })
+        } else {
+            Value::Null
+        }
+    }
 }
 
 impl<'a> From<DataRef<'a>> for Data {
@@ -677,6 +792,57 @@ impl<'a> From<DataRef<'a>> for Data {
     }
 }
 
+/// Parses an ISO 8601 duration (`PT25H30M00S`, `P1DT2H`, `-PT1.5S` ...)
+///
+/// Unlike a time of day, hours are not bounded. Years and months have no fixed
+/// length and are not supported. Units must appear at most once, from the largest to
+/// the smallest, and the duration must have at least one of them (after `T` if any).
+#[cfg(feature = "dates")]
+fn parse_duration_iso(s: &str) -> Option<chrono::Duration> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let s = s.strip_prefix('P')?;
+    let mut in_time = false;
+    let mut start = 0;
+    let mut nanos = 0i64;
+    // unit of the previous component, in seconds
+    let mut last_unit = f64::INFINITY;
+    for (i, c) in s.char_indices() {
+        match c {
+            '0'..='9' | '.' => continue,
+            'T' if !in_time && start == i => {
+                in_time = true;
+                start = i + 1;
+                continue;
+            }
+            _ => (),
+        }
+        let value: f64 = s[start..i].parse().ok()?;
+        let unit = match (c, in_time) {
+            ('W', false) => 7. * 86_400.,
+            ('D', false) => 86_400.,
+            ('H', true) => 3_600.,
+            ('M', true) => 60.,
+            ('S', true) => 1.,
+            _ => return None,
+        };
+        if unit >= last_unit {
+            return None;
+        }
+        last_unit = unit;
+        nanos = nanos.checked_add((value * unit * 1e9).round() as i64)?;
+        start = i + 1;
+    }
+    // no component, trailing number or nothing after `T`
+    if last_unit.is_infinite() || start != s.len() || (in_time && last_unit > 3_600.) {
+        return None;
+    }
+    let duration = chrono::Duration::nanoseconds(nanos);
+    Some(if negative { -duration } else { duration })
+}
+
 /// Excel datetime type. Possible: date, time, datetime, duration.
 /// At this time we can only determine datetime (date and time are datetime too) and duration.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -731,6 +897,13 @@ impl ExcelDateTime {
         self.value
     }
 
+    /// Feed the hasher with the value bits, type and datetime system
+    pub(crate) fn hash_bits<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.value.to_bits());
+        state.write_u8(self.datetime_type as u8);
+        state.write_u8(self.is_1904 as u8);
+    }
+
     /// Try converting data type into a duration
     #[cfg(feature = "dates")]
     pub fn as_duration(&self) -> Option<chrono::Duration> {
@@ -851,6 +1024,55 @@ mod date_tests {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_kind() {
+        let duration = ExcelDateTime::new(1.5, ExcelDateTimeType::TimeDelta, false);
+        let datetime = ExcelDateTime::new(1.5, ExcelDateTimeType::DateTime, false);
+        assert_eq!(Data::Int(1).kind(), DataKind::Int);
+        assert_eq!(Data::String("a".to_string()).kind(), DataKind::String);
+        assert_eq!(Data::DateTime(datetime).kind(), DataKind::DateTime);
+        assert_eq!(Data::DateTime(duration).kind(), DataKind::Duration);
+        assert_eq!(
+            Data::DateTimeIso("2024-01-01".to_string()).kind(),
+            DataKind::DateTime
+        );
+        assert_eq!(Data::Error(CellErrorType::NA).kind(), DataKind::Error);
+        assert_eq!(Data::Empty.kind(), DataKind::Empty);
+        assert_eq!(DataRef::SharedString("a").kind(), DataKind::String);
+        assert_eq!(DataRef::Bool(true).kind(), DataKind::Bool);
+        assert_eq!(DataRef::DateTime(duration).kind(), DataKind::Duration);
+    }
+
+    #[cfg(feature = "dates")]
+    #[test]
+    fn test_as_duration_over_a_day() {
+        let duration = chrono::Duration::seconds(25 * 3600 + 30 * 60);
+        // [h]:mm:ss formatted cell
+        let excel = ExcelDateTime::new(25.5 / 24., ExcelDateTimeType::TimeDelta, false);
+        assert_eq!(Data::DateTime(excel).as_duration(), Some(duration));
+        assert_eq!(
+            Data::DurationIso("PT25H30M00S".to_string()).as_duration(),
+            Some(duration)
+        );
+        assert_eq!(
+            DataRef::DurationIso("P1DT1H30M".to_string()).as_duration(),
+            Some(duration)
+        );
+        assert_eq!(
+            Data::DurationIso("-PT1.5S".to_string()).as_duration(),
+            Some(chrono::Duration::milliseconds(-1500))
+        );
+        assert_eq!(Data::DurationIso("P1Y".to_string()).as_duration(), None);
+        assert_eq!(Data::DurationIso("PT1H30".to_string()).as_duration(), None);
+        for invalid in ["P", "PT", "-P", "P1DT", "PT1H1H", "PT1S1H", "P1D2W"] {
+            assert_eq!(
+                Data::DurationIso(invalid.to_string()).as_duration(),
+                None,
+                "{invalid}"
+            );
+        }
+    }
+
     #[test]
     fn test_partial_eq() {
         assert_eq!(Data::String("value".to_string()), "value");
@@ -875,4 +1097,39 @@ mod tests {
         assert_eq!(DataRef::Bool(true).as_f64(), Some(1.0));
         assert_eq!(DataRef::Bool(false).as_f64(), Some(0.0));
     }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_as_json_value() {
+        use serde_json::json;
+
+        assert_eq!(Data::Empty.as_json_value(), json!(null));
+        assert_eq!(Data::Int(3).as_json_value(), json!(3));
+        assert_eq!(Data::Float(1.5).as_json_value(), json!(1.5));
+        assert_eq!(Data::Float(f64::NAN).as_json_value(), json!(null));
+        assert_eq!(Data::Bool(true).as_json_value(), json!(true));
+        assert_eq!(DataRef::SharedString("a").as_json_value(), json!("a"));
+        assert_eq!(
+            Data::DateTime(ExcelDateTime::new(
+                45292.5,
+                ExcelDateTimeType::DateTime,
+                false
+            ))
+            .as_json_value(),
+            json!("2024-01-01T12:00:00")
+        );
+        assert_eq!(
+            Data::DateTime(ExcelDateTime::new(1.5, ExcelDateTimeType::TimeDelta, false))
+                .as_json_value(),
+            json!("PT129600S")
+        );
+        assert_eq!(
+            Data::DurationIso("PT1H".to_string()).as_json_value(),
+            json!("PT1H")
+        );
+        assert_eq!(
+            Data::Error(CellErrorType::Div0).as_json_value(),
+            json!({ "error": "#DIV/0!" })
+        );
+    }
 }
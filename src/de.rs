@@ -4,10 +4,10 @@ use serde::{forward_to_deserialize_any, Deserialize, Deserializer};
 use std::marker::PhantomData;
 use std::{fmt, slice, str};
 
-use super::{CellErrorType, CellType, Data, Range, Rows};
+use super::{CellErrorType, CellType, Data, Dimensions, Range, Rows};
 
 /// A cell deserialization specific error enum
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum DeError {
     /// Cell out of range
     CellOutOfRange {
@@ -30,6 +30,8 @@ pub enum DeError {
     },
     /// Required header not found
     HeaderNotFound(String),
+    /// Several columns have the same header
+    DuplicateHeader(String),
     /// Serde specific error
     Custom(String),
 }
@@ -54,6 +56,9 @@ impl fmt::Display for DeError {
             DeError::HeaderNotFound(ref header) => {
                 write!(f, "Cannot find header named '{}'", header)
             }
+            DeError::DuplicateHeader(ref header) => {
+                write!(f, "Several columns have the header '{}'", header)
+            }
             DeError::Custom(ref s) => write!(f, "{}", s),
         }
     }
@@ -71,6 +76,19 @@ impl de::Error for DeError {
     }
 }
 
+/// Which column to use when several columns have the same header
+///
+/// See [`RangeDeserializerBuilder::on_duplicate_header`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateHeader {
+    /// Use the leftmost column
+    First,
+    /// Use the rightmost column
+    Last,
+    /// Fail with [`DeError::DuplicateHeader`]
+    Error,
+}
+
 #[derive(Clone)]
 pub enum Headers<'h, H> {
     None,
@@ -82,15 +100,29 @@ pub enum Headers<'h, H> {
 ///
 /// This can be used to optionally parse the first row as a header. Once built,
 /// a `RangeDeserializer`s cannot be changed.
+///
+/// With headers, rows are deserialized as maps keyed by header names (empty cells
+/// being skipped), so `#[serde(flatten)]` can collect the remaining columns, for
+/// instance in a `HashMap<String, Data>`.
 #[derive(Clone)]
 pub struct RangeDeserializerBuilder<'h, H> {
     headers: Headers<'h, H>,
+    header_rows: Option<Vec<u32>>,
+    merged_regions: Vec<Dimensions>,
+    duplicate_header: Option<DuplicateHeader>,
+    skip_empty_rows: bool,
+    whitespace_as_empty: bool,
 }
 
 impl Default for RangeDeserializerBuilder<'static, &'static str> {
     fn default() -> Self {
         RangeDeserializerBuilder {
             headers: Headers::All,
+            header_rows: None,
+            merged_regions: Vec::new(),
+            duplicate_header: None,
+            skip_empty_rows: false,
+            whitespace_as_empty: false,
         }
     }
 }
@@ -170,9 +202,175 @@ impl<'h, H: AsRef<str> + Clone + 'h> RangeDeserializerBuilder<'h, H> {
     pub fn with_headers(headers: &'h [H]) -> Self {
         RangeDeserializerBuilder {
             headers: Headers::Custom(headers),
+            header_rows: None,
+            merged_regions: Vec::new(),
+            duplicate_header: None,
+            skip_empty_rows: false,
+            whitespace_as_empty: false,
         }
     }
 
+    /// Build composite headers out of several rows (e.g. a group row above a subheader row).
+    ///
+    /// Rows are relative to the start of the range. The header of a column is made of the
+    /// non-empty texts of each of these rows, joined with `" / "`. Group titles spanning
+    /// several columns are usually merged cells: pass their regions to
+    /// [`unmerge_with`](Self::unmerge_with) so that every column they cover gets the title.
+    /// Records start after the last header row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use calamine::{Data, DeError, Dimensions, Range, RangeDeserializerBuilder};
+    /// # use std::collections::HashMap;
+    /// fn main() -> Result<(), DeError> {
+    ///     let mut range = Range::new((0, 0), (2, 2));
+    ///     range.set_value((0, 1), Data::from("Sales"));
+    ///     range.set_value((1, 0), Data::from("Region"));
+    ///     range.set_value((1, 1), Data::from("Q1"));
+    ///     range.set_value((1, 2), Data::from("Q2"));
+    ///     range.set_value((2, 0), Data::from("North"));
+    ///     range.set_value((2, 1), Data::Float(10.));
+    ///     range.set_value((2, 2), Data::Float(12.));
+    ///
+    ///     let mut iter = RangeDeserializerBuilder::new()
+    ///         .with_header_rows(&[0, 1])
+    ///         .unmerge_with(&[Dimensions::new((0, 1), (0, 2))])
+    ///         .from_range(&range)?;
+    ///     let record: HashMap<String, Data> = iter.next().unwrap()?;
+    ///     assert_eq!(record["Region"], Data::from("North"));
+    ///     assert_eq!(record["Sales / Q2"], Data::Float(12.));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_header_rows(&mut self, rows: &[u32]) -> &mut Self {
+        self.header_rows = Some(rows.to_vec());
+        self
+    }
+
+    /// Fill merged regions with the value of their top-left cell before reading headers
+    /// and records.
+    ///
+    /// Merged cells only have a value in their top-left cell, this makes a merged header
+    /// name every column it spans and a merged value repeat on every row. Regions are in
+    /// absolute positions, as returned by `worksheet_merge_cells`. Only empty cells are
+    /// filled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use calamine::{Data, DeError, Dimensions, Range, RangeDeserializerBuilder};
+    /// fn main() -> Result<(), DeError> {
+    ///     let mut range = Range::new((0, 0), (1, 1));
+    ///     range.set_value((0, 0), Data::from("value"));
+    ///     range.set_value((1, 0), Data::Float(1.));
+    ///     range.set_value((1, 1), Data::Float(2.));
+    ///     let merged = [Dimensions::new((0, 0), (0, 1))];
+    ///
+    ///     let mut iter = RangeDeserializerBuilder::new()
+    ///         .unmerge_with(&merged)
+    ///         .from_range(&range)?;
+    ///     let record: Vec<f64> = iter.next().unwrap()?;
+    ///     assert_eq!(record, [1., 2.]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn unmerge_with(&mut self, merged_regions: &[Dimensions]) -> &mut Self {
+        self.merged_regions = merged_regions.to_vec();
+        self
+    }
+
+    /// Decide what to do when several columns have the same (non-empty) header.
+    ///
+    /// By default, selected headers ([`with_headers`](Self::with_headers)) use the
+    /// first matching column, and all columns are kept otherwise, so maps get the
+    /// duplicated key twice. With a policy, only one column per header is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use calamine::{Data, DeError, DuplicateHeader, Range, RangeDeserializerBuilder};
+    /// # use std::collections::HashMap;
+    /// fn main() -> Result<(), DeError> {
+    ///     let mut range = Range::new((0, 0), (1, 1));
+    ///     range.set_value((0, 0), Data::from("value"));
+    ///     range.set_value((0, 1), Data::from("value"));
+    ///     range.set_value((1, 0), Data::Float(1.));
+    ///     range.set_value((1, 1), Data::Float(2.));
+    ///
+    ///     let mut iter = RangeDeserializerBuilder::new()
+    ///         .on_duplicate_header(DuplicateHeader::First)
+    ///         .from_range(&range)?;
+    ///     let record: HashMap<String, f64> = iter.next().unwrap()?;
+    ///     assert_eq!(record["value"], 1.);
+    ///
+    ///     let error = RangeDeserializerBuilder::new()
+    ///         .on_duplicate_header(DuplicateHeader::Error)
+    ///         .from_range::<_, HashMap<String, f64>>(&range)
+    ///         .err();
+    ///     assert_eq!(error, Some(DeError::DuplicateHeader("value".to_string())));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn on_duplicate_header(&mut self, policy: DuplicateHeader) -> &mut Self {
+        self.duplicate_header = Some(policy);
+        self
+    }
+
+    /// Decide whether to skip rows where every cell is empty (`false` by default).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use calamine::{Data, DeError, Range, RangeDeserializerBuilder};
+    /// fn main() -> Result<(), DeError> {
+    ///     let mut range = Range::new((0, 0), (3, 0));
+    ///     range.set_value((0, 0), Data::from("value"));
+    ///     range.set_value((1, 0), Data::Float(1.));
+    ///     range.set_value((3, 0), Data::Float(2.));
+    ///
+    ///     let values = RangeDeserializerBuilder::new()
+    ///         .skip_empty_rows(true)
+    ///         .from_range(&range)?
+    ///         .collect::<Result<Vec<(f64,)>, _>>()?;
+    ///     assert_eq!(values, [(1.,), (2.,)]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn skip_empty_rows(&mut self, yes: bool) -> &mut Self {
+        self.skip_empty_rows = yes;
+        self
+    }
+
+    /// Decide whether cells containing only whitespaces are considered empty (`false` by
+    /// default).
+    ///
+    /// Empty cells deserialize `Option` fields as `None`. When set, so do blank strings,
+    /// and rows only made of blank cells are skipped with
+    /// [`skip_empty_rows`](Self::skip_empty_rows).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use calamine::{Data, DeError, Range, RangeDeserializerBuilder};
+    /// fn main() -> Result<(), DeError> {
+    ///     let mut range = Range::new((0, 0), (1, 0));
+    ///     range.set_value((0, 0), Data::from("value"));
+    ///     range.set_value((1, 0), Data::from("   "));
+    ///
+    ///     let mut iter = RangeDeserializerBuilder::new()
+    ///         .whitespace_as_empty(true)
+    ///         .from_range(&range)?;
+    ///     let (value,): (Option<f64>,) = iter.next().unwrap()?;
+    ///     assert_eq!(value, None);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn whitespace_as_empty(&mut self, yes: bool) -> &mut Self {
+        self.whitespace_as_empty = yes;
+        self
+    }
+
     /// Build a `RangeDeserializer` from this configuration.
     ///
     /// # Example
@@ -319,9 +517,12 @@ where
 {
     column_indexes: Vec<usize>,
     headers: Option<Vec<String>>,
+    skip_empty_rows: bool,
+    whitespace_as_empty: bool,
+    range: &'cell Range<T>,
+    merged_regions: Vec<Dimensions>,
     rows: Rows<'cell, T>,
     current_pos: (u32, u32),
-    end_pos: (u32, u32),
     _priv: PhantomData<D>,
 }
 
@@ -337,17 +538,16 @@ where
         let mut rows = range.rows();
 
         let mut current_pos = range.start().unwrap_or((0, 0));
-        let end_pos = range.end().unwrap_or((0, 0));
 
         let (column_indexes, headers) = match builder.headers {
             Headers::None => ((0..range.width()).collect(), None),
             Headers::All => {
-                if let Some(row) = rows.next() {
-                    let all_indexes = (0..row.len()).collect::<Vec<_>>();
-                    let all_headers = {
-                        let de = RowDeserializer::new(&all_indexes, None, row, current_pos);
-                        current_pos.0 += 1;
-                        Deserialize::deserialize(de)?
+                if let Some(all_headers) =
+                    read_headers(range, &mut rows, &mut current_pos, builder)?
+                {
+                    let all_indexes = match builder.duplicate_header {
+                        Some(policy) => unique_header_indexes(&all_headers, policy)?,
+                        None => (0..all_headers.len()).collect(),
                     };
                     (all_indexes, Some(all_headers))
                 } else {
@@ -355,19 +555,28 @@ where
                 }
             }
             Headers::Custom(headers) => {
-                if let Some(row) = rows.next() {
-                    let all_indexes = (0..row.len()).collect::<Vec<_>>();
-                    let de = RowDeserializer::new(&all_indexes, None, row, current_pos);
-                    current_pos.0 += 1;
-                    let all_headers: Vec<String> = Deserialize::deserialize(de)?;
+                if let Some(all_headers) =
+                    read_headers(range, &mut rows, &mut current_pos, builder)?
+                {
                     let custom_indexes = headers
                         .iter()
                         .map(|h| h.as_ref().trim())
                         .map(|h| {
-                            all_headers
+                            let mut positions = all_headers
                                 .iter()
-                                .position(|header| header.trim() == h)
-                                .ok_or_else(|| DeError::HeaderNotFound(h.to_owned()))
+                                .enumerate()
+                                .filter(|(_, header)| header.trim() == h)
+                                .map(|(i, _)| i);
+                            let first = positions
+                                .next()
+                                .ok_or_else(|| DeError::HeaderNotFound(h.to_owned()))?;
+                            match (builder.duplicate_header, positions.next_back()) {
+                                (Some(DuplicateHeader::Last), Some(last)) => Ok(last),
+                                (Some(DuplicateHeader::Error), Some(_)) => {
+                                    Err(DeError::DuplicateHeader(h.to_owned()))
+                                }
+                                _ => Ok(first),
+                            }
                         })
                         .collect::<Result<Vec<_>, DeError>>()?;
                     (custom_indexes, Some(all_headers))
@@ -380,44 +589,198 @@ where
         Ok(RangeDeserializer {
             column_indexes,
             headers,
+            skip_empty_rows: builder.skip_empty_rows,
+            whitespace_as_empty: builder.whitespace_as_empty,
+            range,
+            merged_regions: builder.merged_regions.clone(),
             rows,
             current_pos,
-            end_pos,
             _priv: PhantomData,
         })
     }
 }
 
-impl<'cell, T, D> Iterator for RangeDeserializer<'cell, T, D>
+impl<'cell, T, D> RangeDeserializer<'cell, T, D>
 where
     T: ToCellDeserializer<'cell>,
     D: DeserializeOwned,
 {
-    type Item = Result<D, DeError>;
+    /// Deserialize the next record, along with the row it comes from.
+    ///
+    /// The row is the absolute (0 based) row index in the worksheet, which helps
+    /// reporting errors to users: add 1 to get the row number displayed by Excel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use calamine::{open_workbook, Error, Xlsx, Reader, RangeDeserializerBuilder};
+    /// fn main() -> Result<(), Error> {
+    ///     let path = format!("{}/tests/temperature.xlsx", env!("CARGO_MANIFEST_DIR"));
+    ///     let mut workbook: Xlsx<_> = open_workbook(path)?;
+    ///     let range = workbook.worksheet_range("Sheet1")?;
+    ///     let mut iter = RangeDeserializerBuilder::new().from_range(&range)?;
+    ///
+    ///     let (row, record) = iter.next_with_row().expect("at least one record");
+    ///     let (label, _value): (String, f64) = record?;
+    ///     assert_eq!((row, label.as_str()), (1, "celsius"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn next_with_row(&mut self) -> Option<(u32, Result<D, DeError>)> {
+        loop {
+            let row = self.rows.next()?;
+            let pos = self.current_pos;
+            self.current_pos.0 += 1;
+            let whitespace_as_empty = self.whitespace_as_empty;
+            let fills = merged_fills(self.range, &self.merged_regions, pos.0);
+            if self.skip_empty_rows
+                && fills.is_empty()
+                && row
+                    .iter()
+                    .all(|c| c.is_empty() || (whitespace_as_empty && c.is_blank()))
+            {
+                continue;
+            }
+            let headers = self.headers.as_deref();
+            let de = RowDeserializer::new(&self.column_indexes, headers, row, pos)
+                .whitespace_as_empty(whitespace_as_empty)
+                .fills(fills);
+            return Some((pos.0, Deserialize::deserialize(de)));
+        }
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let RangeDeserializer {
-            ref column_indexes,
-            ref headers,
-            ref mut rows,
-            mut current_pos,
-            ..
-        } = *self;
-
-        if let Some(row) = rows.next() {
+/// Reads the header names, consuming the header rows
+///
+/// Returns `None` if the range doesn't have enough rows
+fn read_headers<'cell, 'h, T: ToCellDeserializer<'cell>, H>(
+    range: &'cell Range<T>,
+    rows: &mut Rows<'cell, T>,
+    current_pos: &mut (u32, u32),
+    builder: &RangeDeserializerBuilder<'h, H>,
+) -> Result<Option<Vec<String>>, DeError> {
+    let merged_regions = &builder.merged_regions;
+    let header_rows = match builder.header_rows {
+        Some(ref header_rows) => header_rows,
+        None => {
+            let Some(row) = rows.next() else {
+                return Ok(None);
+            };
+            let all_indexes = (0..row.len()).collect::<Vec<_>>();
+            let fills = merged_fills(range, merged_regions, current_pos.0);
+            let de = RowDeserializer::new(&all_indexes, None, row, *current_pos).fills(fills);
             current_pos.0 += 1;
-            let headers = headers.as_ref().map(|h| &**h);
-            let de = RowDeserializer::new(column_indexes, headers, row, current_pos);
-            Some(Deserialize::deserialize(de))
-        } else {
+            return Deserialize::deserialize(de).map(Some);
+        }
+    };
+    let Some(&last) = header_rows.iter().max() else {
+        return Ok(None);
+    };
+    if last as usize >= range.height() {
+        // no record can follow the missing header rows
+        rows.for_each(drop);
+        return Ok(None);
+    }
+    let all_indexes = (0..range.width()).collect::<Vec<_>>();
+    let mut headers = vec![String::new(); range.width()];
+    for &r in header_rows {
+        let row = range.rows().nth(r as usize).unwrap_or_default();
+        let pos = (current_pos.0 + r, current_pos.1);
+        let fills = merged_fills(range, merged_regions, pos.0);
+        let de = RowDeserializer::new(&all_indexes, None, row, pos).fills(fills);
+        let texts: Vec<String> = Deserialize::deserialize(de)?;
+        for (header, text) in headers.iter_mut().zip(&texts) {
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            if !header.is_empty() {
+                header.push_str(" / ");
+            }
+            header.push_str(text);
+        }
+    }
+    for _ in 0..=last {
+        rows.next();
+    }
+    current_pos.0 += last + 1;
+    Ok(Some(headers))
+}
+
+/// Gets the indexes of the columns to keep so that each non-empty header appears once
+fn unique_header_indexes(
+    headers: &[String],
+    policy: DuplicateHeader,
+) -> Result<Vec<usize>, DeError> {
+    let mut indexes: Vec<usize> = Vec::with_capacity(headers.len());
+    for (i, header) in headers.iter().enumerate() {
+        let header = header.trim();
+        let previous = if header.is_empty() {
             None
+        } else {
+            indexes.iter().position(|&j| headers[j].trim() == header)
+        };
+        match (previous, policy) {
+            (None, _) => indexes.push(i),
+            (Some(_), DuplicateHeader::First) => (),
+            (Some(p), DuplicateHeader::Last) => indexes[p] = i,
+            (Some(_), DuplicateHeader::Error) => {
+                return Err(DeError::DuplicateHeader(header.to_owned()))
+            }
         }
     }
+    Ok(indexes)
+}
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = (self.end_pos.0 - self.current_pos.0) as usize;
+/// Gets the values filling the empty cells of a row which are part of a merged region,
+/// along with their column index relative to the range start
+fn merged_fills<'cell, T: ToCellDeserializer<'cell>>(
+    range: &'cell Range<T>,
+    merged_regions: &[Dimensions],
+    row: u32,
+) -> Vec<(usize, &'cell T)> {
+    let (Some(start), Some(end)) = (range.start(), range.end()) else {
+        return Vec::new();
+    };
+    let mut fills = Vec::new();
+    for region in merged_regions {
+        if row < region.start.0 || row > region.end.0 {
+            continue;
+        }
+        let Some(value) = range.get_value(region.start) else {
+            continue;
+        };
+        let first = region.start.1.max(start.1);
+        let last = region.end.1.min(end.1);
+        for col in first..=last {
+            if (row, col) != region.start
+                && range.get_value((row, col)).map_or(true, |c| c.is_empty())
+            {
+                fills.push(((col - start.1) as usize, value));
+            }
+        }
+    }
+    fills
+}
+
+impl<'cell, T, D> Iterator for RangeDeserializer<'cell, T, D>
+where
+    T: ToCellDeserializer<'cell>,
+    D: DeserializeOwned,
+{
+    type Item = Result<D, DeError>;
 
-        (remaining, Some(remaining))
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_row().map(|(_, record)| record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.rows.len();
+        if self.skip_empty_rows {
+            (0, Some(remaining))
+        } else {
+            (remaining, Some(remaining))
+        }
     }
 }
 
@@ -427,6 +790,8 @@ struct RowDeserializer<'header, 'cell, T> {
     iter: slice::Iter<'header, usize>, // iterator over column indexes
     peek: Option<usize>,
     pos: (u32, u32),
+    whitespace_as_empty: bool,
+    fills: Vec<(usize, &'cell T)>,
 }
 
 impl<'header, 'cell, T> RowDeserializer<'header, 'cell, T>
@@ -445,14 +810,47 @@ where
             cells,
             pos,
             peek: None,
+            whitespace_as_empty: false,
+            fills: Vec::new(),
         }
     }
 
+    fn fills(mut self, fills: Vec<(usize, &'cell T)>) -> Self {
+        self.fills = fills;
+        self
+    }
+
+    /// Gets the cell at a column index, merged regions being filled
+    fn cell(&self, i: usize) -> &'cell T {
+        self.fills
+            .iter()
+            .find(|(col, _)| *col == i)
+            .map_or(&self.cells[i], |(_, value)| value)
+    }
+
+    fn whitespace_as_empty(mut self, yes: bool) -> Self {
+        self.whitespace_as_empty = yes;
+        self
+    }
+
     fn has_headers(&self) -> bool {
         self.headers.is_some()
     }
 }
 
+impl<'cell, T: ToCellDeserializer<'cell>> RowDeserializer<'_, 'cell, T> {
+    fn is_empty(&self, cell: &T) -> bool {
+        cell.is_empty() || (self.whitespace_as_empty && cell.is_blank())
+    }
+
+    fn cell_deserializer(&self, cell: &'cell T) -> CellDeserializer<T::Deserializer> {
+        CellDeserializer {
+            inner: cell.to_cell_deserializer(self.pos),
+            empty: self.is_empty(cell),
+        }
+    }
+}
+
 impl<'de, 'header, 'cell, T> serde::Deserializer<'de> for RowDeserializer<'header, 'cell, T>
 where
     'header: 'de,
@@ -508,9 +906,9 @@ where
     where
         D: DeserializeSeed<'de>,
     {
-        match self.iter.next().map(|i| &self.cells[*i]) {
+        match self.iter.next().map(|i| self.cell(*i)) {
             Some(value) => {
-                let de = value.to_cell_deserializer(self.pos);
+                let de = self.cell_deserializer(value);
                 seed.deserialize(de).map(Some)
             }
             None => Ok(None),
@@ -541,8 +939,8 @@ where
             .headers
             .expect("Cannot map-deserialize range without headers");
 
-        for i in self.iter.by_ref() {
-            if !self.cells[*i].is_empty() {
+        while let Some(i) = self.iter.next() {
+            if !self.is_empty(self.cell(*i)) {
                 self.peek = Some(*i);
                 let de = BorrowedStrDeserializer::<Self::Error>::new(&headers[*i]);
                 return seed.deserialize(de).map(Some);
@@ -558,13 +956,60 @@ where
         let cell = self
             .peek
             .take()
-            .map(|i| &self.cells[i])
+            .map(|i| self.cell(i))
             .ok_or(DeError::UnexpectedEndOfRow { pos: self.pos })?;
-        let de = cell.to_cell_deserializer(self.pos);
+        let de = self.cell_deserializer(cell);
         seed.deserialize(de)
     }
 }
 
+/// A cell deserializer which deserializes empty cells as `None` options
+///
+/// What is considered empty depends on the `RangeDeserializerBuilder` configuration.
+struct CellDeserializer<D> {
+    inner: D,
+    empty: bool,
+}
+
+macro_rules! forward_to_inner {
+    ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+                self.inner.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: serde::Deserializer<'de, Error = DeError>> serde::Deserializer<'de>
+    for CellDeserializer<D>
+{
+    type Error = DeError;
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.empty {
+            visitor.visit_none()
+        } else {
+            self.inner.deserialize_option(visitor)
+        }
+    }
+
+    forward_to_inner! {
+        deserialize_any() deserialize_bool() deserialize_i8() deserialize_i16()
+        deserialize_i32() deserialize_i64() deserialize_u8() deserialize_u16()
+        deserialize_u32() deserialize_u64() deserialize_f32() deserialize_f64()
+        deserialize_char() deserialize_str() deserialize_string() deserialize_bytes()
+        deserialize_byte_buf() deserialize_unit() deserialize_seq() deserialize_map()
+        deserialize_identifier() deserialize_ignored_any()
+        deserialize_unit_struct(name: &'static str)
+        deserialize_newtype_struct(name: &'static str)
+        deserialize_tuple(len: usize)
+        deserialize_tuple_struct(name: &'static str, len: usize)
+        deserialize_struct(name: &'static str, fields: &'static [&'static str])
+        deserialize_enum(name: &'static str, variants: &'static [&'static str])
+    }
+}
+
 /// Constructs a deserializer for a `CellType`.
 pub trait ToCellDeserializer<'a>: CellType {
     /// The deserializer.
@@ -575,6 +1020,11 @@ pub trait ToCellDeserializer<'a>: CellType {
 
     /// Assess if the cell is empty.
     fn is_empty(&self) -> bool;
+
+    /// Assess if the cell is empty or only contains whitespaces.
+    fn is_blank(&self) -> bool {
+        self.is_empty()
+    }
 }
 
 impl<'a> ToCellDeserializer<'a> for Data {
@@ -591,6 +1041,14 @@ impl<'a> ToCellDeserializer<'a> for Data {
     fn is_empty(&self) -> bool {
         matches!(self, Data::Empty)
     }
+
+    fn is_blank(&self) -> bool {
+        match self {
+            Data::Empty => true,
+            Data::String(s) => s.trim().is_empty(),
+            _ => false,
+        }
+    }
 }
 
 macro_rules! deserialize_num {
@@ -1,5 +1,7 @@
 //! A module to provide a convenient wrapper around all error types
 
+use crate::utils::io_error_eq;
+
 /// A struct to handle any error and a message
 #[derive(Debug)]
 pub enum Error {
@@ -16,6 +18,9 @@ pub enum Error {
     Xlsx(crate::xlsx::XlsxError),
     /// vba specific error
     Vba(crate::vba::VbaError),
+    /// csv specific error
+    #[cfg(feature = "csv")]
+    Csv(crate::csv::CsvError),
     /// cfb specific error
     De(crate::de::DeError),
 
@@ -29,6 +34,8 @@ from_err!(crate::xls::XlsError, Error, Xls);
 from_err!(crate::xlsb::XlsbError, Error, Xlsb);
 from_err!(crate::xlsx::XlsxError, Error, Xlsx);
 from_err!(crate::vba::VbaError, Error, Vba);
+#[cfg(feature = "csv")]
+from_err!(crate::csv::CsvError, Error, Csv);
 from_err!(crate::de::DeError, Error, De);
 from_err!(&'static str, Error, Msg);
 
@@ -41,6 +48,8 @@ impl std::fmt::Display for Error {
             Error::Xlsx(e) => write!(f, "Xlsx error: {}", e),
             Error::Xlsb(e) => write!(f, "Xlsb error: {}", e),
             Error::Vba(e) => write!(f, "Vba error: {}", e),
+            #[cfg(feature = "csv")]
+            Error::Csv(e) => write!(f, "Csv error: {}", e),
             Error::De(e) => write!(f, "Deserializer error: {}", e),
             Error::Msg(msg) => write!(f, "{}", msg),
         }
@@ -56,8 +65,28 @@ impl std::error::Error for Error {
             Error::Xlsb(e) => Some(e),
             Error::Xlsx(e) => Some(e),
             Error::Vba(e) => Some(e),
+            #[cfg(feature = "csv")]
+            Error::Csv(e) => Some(e),
             Error::De(e) => Some(e),
             Error::Msg(_) => None,
         }
     }
 }
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Error::Io(a), Error::Io(b)) => io_error_eq(a, b),
+            (Error::Ods(a), Error::Ods(b)) => a == b,
+            (Error::Xls(a), Error::Xls(b)) => a == b,
+            (Error::Xlsb(a), Error::Xlsb(b)) => a == b,
+            (Error::Xlsx(a), Error::Xlsx(b)) => a == b,
+            (Error::Vba(a), Error::Vba(b)) => a == b,
+            #[cfg(feature = "csv")]
+            (Error::Csv(a), Error::Csv(b)) => a == b,
+            (Error::De(a), Error::De(b)) => a == b,
+            (Error::Msg(a), Error::Msg(b)) => a == b,
+            _ => false,
+        }
+    }
+}
@@ -5,6 +5,7 @@ pub enum CellFormat {
     Other,
     DateTime,
     TimeDelta,
+    Text,
 }
 
 /// Check excel number format is datetime
@@ -23,6 +24,7 @@ pub fn detect_custom_number_format(format: &str) -> CellFormat {
             (_, _, true, _, _) => (),
             ('"', _, _, _, _) => is_quote = true,
             (';', ..) => return CellFormat::Other, // first format only
+            ('@', ..) => return CellFormat::Text,
             ('[', ..) => brackets += 1,
             (']', .., 1) if hms => return CellFormat::TimeDelta, // if closing
             (']', ..) => brackets = brackets.saturating_sub(1),
@@ -70,10 +72,50 @@ pub fn builtin_format_by_id(id: &[u8]) -> CellFormat {
         b"47" => CellFormat::DateTime,
         // [h]:mm:ss
         b"46" => CellFormat::TimeDelta,
+        // @
+        b"49" => CellFormat::Text,
         _ => CellFormat::Other
 }
 }
 
+/// Get the format code of a builtin number format
+///
+/// Only the ids defined by ECMA-376 (18.8.30) are known, locale dependent ids are not
+pub fn builtin_format_code_by_id(id: &[u8]) -> Option<&'static str> {
+    let code = match id {
+        b"0" => "General",
+        b"1" => "0",
+        b"2" => "0.00",
+        b"3" => "#,##0",
+        b"4" => "#,##0.00",
+        b"9" => "0%",
+        b"10" => "0.00%",
+        b"11" => "0.00E+00",
+        b"12" => "# ?/?",
+        b"13" => "# ??/??",
+        b"14" => "mm-dd-yy",
+        b"15" => "d-mmm-yy",
+        b"16" => "d-mmm",
+        b"17" => "mmm-yy",
+        b"18" => "h:mm AM/PM",
+        b"19" => "h:mm:ss AM/PM",
+        b"20" => "h:mm",
+        b"21" => "h:mm:ss",
+        b"22" => "m/d/yy h:mm",
+        b"37" => "#,##0 ;(#,##0)",
+        b"38" => "#,##0 ;[Red](#,##0)",
+        b"39" => "#,##0.00;(#,##0.00)",
+        b"40" => "#,##0.00;[Red](#,##0.00)",
+        b"45" => "mm:ss",
+        b"46" => "[h]:mm:ss",
+        b"47" => "mmss.0",
+        b"48" => "##0.0E+0",
+        b"49" => "@",
+        _ => return None,
+    };
+    Some(code)
+}
+
 /// Check if code corresponds to builtin date format
 ///
 /// See `is_builtin_date_format_id`
@@ -81,6 +123,7 @@ pub fn builtin_format_by_code(code: u16) -> CellFormat {
     match code {
         14..=22 | 45 | 47 => CellFormat::DateTime,
         46 => CellFormat::TimeDelta,
+        49 => CellFormat::Text,
         _ => CellFormat::Other,
     }
 }
@@ -214,4 +257,8 @@ fn test_is_date_format() {
         detect_custom_number_format("#,##0.00\\ _M\"H\"_);[Red]#,##0.00\\ _M\"S\"_)"),
         CellFormat::Other
     );
+    assert_eq!(detect_custom_number_format("@"), CellFormat::Text);
+    assert_eq!(detect_custom_number_format("\"ref \"@"), CellFormat::Text);
+    assert_eq!(detect_custom_number_format("\"@\"0"), CellFormat::Other);
+    assert_eq!(detect_custom_number_format("0;0;0;@"), CellFormat::Other);
 }
@@ -62,6 +62,8 @@ mod utils;
 
 mod auto;
 mod cfb;
+#[cfg(feature = "csv")]
+mod csv;
 mod datatype;
 mod formats;
 mod ods;
@@ -76,27 +78,40 @@ pub mod vba;
 use serde::de::{Deserialize, DeserializeOwned, Deserializer};
 use std::borrow::Cow;
 use std::cmp::{max, min};
+use std::collections::HashMap;
 use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, Read, Seek};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::marker::PhantomData;
 use std::ops::{Index, IndexMut};
 use std::path::Path;
 
-pub use crate::auto::{open_workbook_auto, open_workbook_auto_from_rs, Sheets};
-pub use crate::datatype::{Data, DataRef, DataType, ExcelDateTime, ExcelDateTimeType};
-pub use crate::de::{DeError, RangeDeserializer, RangeDeserializerBuilder, ToCellDeserializer};
+pub use crate::auto::{
+    open_workbook_auto, open_workbook_auto_from_bytes, open_workbook_auto_from_rs, Sheets,
+};
+#[cfg(feature = "csv")]
+pub use crate::csv::{Csv, CsvError, CSV_SHEET_NAME};
+pub use crate::datatype::{Data, DataKind, DataRef, DataType, ExcelDateTime, ExcelDateTimeType};
+pub use crate::de::{
+    DeError, DuplicateHeader, RangeDeserializer, RangeDeserializerBuilder, ToCellDeserializer,
+};
 pub use crate::errors::Error;
 pub use crate::ods::{Ods, OdsError};
 pub use crate::xls::{Xls, XlsError, XlsOptions};
-pub use crate::xlsb::{Xlsb, XlsbError};
-pub use crate::xlsx::{Xlsx, XlsxError};
+pub use crate::xlsb::{Xlsb, XlsbError, XlsbOptions};
+pub use crate::xlsx::{
+    ChartSeries, DataConnection, HeaderFooter, MergeRole, Orientation, OutlineLevels, PageMargins,
+    PageSetup, RichValue, SheetProtection, WorkbookProtection, XlReader, Xlsx, XlsxError,
+    XlsxOptions,
+};
 
 use crate::vba::VbaProject;
 
 // https://msdn.microsoft.com/en-us/library/office/ff839168.aspx
 /// An enum to represent all different errors that can appear as
 /// a value in a worksheet cell
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Hash)]
 pub enum CellErrorType {
     /// Division by 0 error
     Div0,
@@ -114,6 +129,10 @@ pub enum CellErrorType {
     Value,
     /// Getting data
     GettingData,
+    /// Dynamic array spill error
+    Spill,
+    /// Calculation error
+    Calc,
 }
 
 impl fmt::Display for CellErrorType {
@@ -127,6 +146,8 @@ impl fmt::Display for CellErrorType {
             CellErrorType::Ref => write!(f, "#REF!"),
             CellErrorType::Value => write!(f, "#VALUE!"),
             CellErrorType::GettingData => write!(f, "#DATA!"),
+            CellErrorType::Spill => write!(f, "#SPILL!"),
+            CellErrorType::Calc => write!(f, "#CALC!"),
         }
     }
 }
@@ -227,6 +248,76 @@ pub enum HeaderRow {
     Row(u32),
 }
 
+/// Decimal and grouping separators used to read numbers stored as text
+///
+/// Localized applications may export numbers as text such as `"1.234,56"`.
+///
+/// # Examples
+/// ```
+/// use calamine::NumberSeparators;
+///
+/// let separators = NumberSeparators::new(',', Some('.'));
+/// assert_eq!(separators.parse("1.234,56"), Some(1234.56));
+/// assert_eq!(separators.parse("1.2.3"), None);
+/// assert_eq!(separators.parse("007"), Some(7.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberSeparators {
+    /// Decimal separator
+    pub decimal: char,
+    /// Optional grouping (thousands) separator
+    pub grouping: Option<char>,
+}
+
+impl NumberSeparators {
+    /// Creates new separators
+    pub fn new(decimal: char, grouping: Option<char>) -> Self {
+        NumberSeparators { decimal, grouping }
+    }
+
+    /// Parse a number written with these separators
+    ///
+    /// Only plain decimal numbers (optional sign, digits, grouping separators
+    /// every 3 digits and decimal separator) are accepted.
+    ///
+    /// Leading zeros are not kept: codes such as `"007"` become `7.0`.
+    pub fn parse(&self, s: &str) -> Option<f64> {
+        let s = s.trim();
+        let (sign, digits) = match s.strip_prefix('-') {
+            Some(d) => ("-", d),
+            None => ("", s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (int, frac) = match digits.split_once(self.decimal) {
+            Some((int, frac)) => (int, frac),
+            None => (digits, ""),
+        };
+        let int = match self.grouping {
+            Some(g) if int.contains(g) => {
+                let mut groups = int.split(g);
+                let mut int = groups.next().unwrap_or_default().to_string();
+                if int.is_empty() || int.len() > 3 {
+                    return None;
+                }
+                for group in groups {
+                    if group.len() != 3 {
+                        return None;
+                    }
+                    int.push_str(group);
+                }
+                int
+            }
+            _ => int.to_string(),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+        if !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        format!("{sign}{int}.{frac}").parse().ok()
+    }
+}
+
 // FIXME `Reader` must only be seek `Seek` for `Xls::xls`. Because of the present API this limits
 // the kinds of readers (other) data in formats can be read from.
 /// A trait to share spreadsheets reader functions across different `FileType`s
@@ -277,6 +368,35 @@ where
             .collect()
     }
 
+    /// Get the number of sheets of this workbook
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Xlsx, open_workbook, Reader};
+    ///
+    /// # let path = format!("{}/tests/issue3.xlsm", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// assert_eq!(workbook.sheet_count(), 3);
+    /// ```
+    fn sheet_count(&self) -> usize {
+        self.metadata().sheets.len()
+    }
+
+    /// Get the name of the nth sheet, in workbook order
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Xlsx, open_workbook, Reader};
+    ///
+    /// # let path = format!("{}/tests/issue3.xlsm", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// assert_eq!(workbook.sheet_name_at(0), Some("Sheet1"));
+    /// assert_eq!(workbook.sheet_name_at(3), None);
+    /// ```
+    fn sheet_name_at(&self, n: usize) -> Option<&str> {
+        self.metadata().sheets.get(n).map(|s| s.name.as_str())
+    }
+
     /// Fetch all sheets metadata
     fn sheets_metadata(&self) -> &[Sheet] {
         &self.metadata().sheets
@@ -290,15 +410,75 @@ where
     /// Get the nth worksheet. Shortcut for getting the nth
     /// sheet_name, then the corresponding worksheet.
     fn worksheet_range_at(&mut self, n: usize) -> Option<Result<Range<Data>, Self::Error>> {
-        let name = self.sheet_names().get(n)?.to_string();
+        let name = self.sheet_name_at(n)?.to_string();
         Some(self.worksheet_range(&name))
     }
 
+    /// Get a lazy iterator over all worksheets, in workbook order
+    ///
+    /// Unlike [`worksheets`](Reader::worksheets), each sheet is only read when the iterator
+    /// is advanced, so already processed sheets can be freed, and errors are reported.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Xlsx, open_workbook, Reader};
+    ///
+    /// # let path = format!("{}/tests/issue3.xlsm", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// for (name, range) in workbook.worksheet_ranges() {
+    ///     println!("{name}: {:?}", range.map(|r| r.get_size()));
+    /// }
+    /// ```
+    fn worksheet_ranges(&mut self) -> WorksheetRanges<'_, RS, Self>
+    where
+        Self: Sized,
+    {
+        WorksheetRanges {
+            reader: self,
+            index: 0,
+            _rs: PhantomData,
+        }
+    }
+
     /// Get all pictures, tuple as (ext: String, data: Vec<u8>)
     #[cfg(feature = "picture")]
     fn pictures(&self) -> Option<Vec<(String, Vec<u8>)>>;
 }
 
+/// A lazy iterator over the worksheets of a workbook, see [`Reader::worksheet_ranges`]
+pub struct WorksheetRanges<'a, RS, R> {
+    reader: &'a mut R,
+    index: usize,
+    _rs: PhantomData<RS>,
+}
+
+impl<'a, RS, R> Iterator for WorksheetRanges<'a, RS, R>
+where
+    RS: Read + Seek,
+    R: Reader<RS>,
+{
+    type Item = (String, Result<Range<Data>, R::Error>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let name = self.reader.sheet_name_at(self.index)?.to_string();
+        self.index += 1;
+        let range = self.reader.worksheet_range(&name);
+        Some((name, range))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.reader.sheet_count().saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+impl<'a, RS, R> ExactSizeIterator for WorksheetRanges<'a, RS, R>
+where
+    RS: Read + Seek,
+    R: Reader<RS>,
+{
+}
+
 /// A trait to share spreadsheets reader functions across different `FileType`s
 pub trait ReaderRef<RS>: Reader<RS>
 where
@@ -317,7 +497,7 @@ where
     /// This is implemented only for [`calamine::Xlsb`] and [`calamine::Xlsx`], as Xls and Ods formats
     /// do not support lazy iteration.
     fn worksheet_range_at_ref(&mut self, n: usize) -> Option<Result<Range<DataRef>, Self::Error>> {
-        let name = self.sheet_names().get(n)?.to_string();
+        let name = self.sheet_name_at(n)?.to_string();
         Some(self.worksheet_range_ref(&name))
     }
 }
@@ -341,12 +521,53 @@ where
     R::new(rs)
 }
 
+/// Convenient function to open a workbook from in-memory bytes
+///
+/// # Examples
+/// ```
+/// use calamine::{open_workbook_from_bytes, Reader, Xlsx};
+///
+/// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+/// let bytes = std::fs::read(path).unwrap();
+/// let mut workbook: Xlsx<_> = open_workbook_from_bytes(bytes).unwrap();
+/// assert!(workbook.worksheet_range("issue2").is_ok());
+/// ```
+pub fn open_workbook_from_bytes<R>(bytes: Vec<u8>) -> Result<R, R::Error>
+where
+    R: Reader<Cursor<Vec<u8>>>,
+{
+    R::new(Cursor::new(bytes))
+}
+
+/// Convenient function to open a workbook from a borrowed byte slice
+///
+/// This is useful in async code, where the content can be fully read first
+/// (e.g. with `read_to_end`) then parsed synchronously.
+///
+/// # Examples
+/// ```
+/// use calamine::{open_workbook_from_slice, Reader, Xlsx};
+///
+/// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+/// let bytes = std::fs::read(path).unwrap();
+/// let mut workbook: Xlsx<_> = open_workbook_from_slice(&bytes).unwrap();
+/// assert!(workbook.worksheet_range("issue2").is_ok());
+/// ```
+pub fn open_workbook_from_slice<'a, R>(bytes: &'a [u8]) -> Result<R, R::Error>
+where
+    R: Reader<Cursor<&'a [u8]>>,
+{
+    R::new(Cursor::new(bytes))
+}
+
 /// A trait to constrain cells
 pub trait CellType: Default + Clone + PartialEq {}
 
 impl CellType for Data {}
 impl<'a> CellType for DataRef<'a> {}
 impl CellType for String {}
+impl CellType for RichValue {}
+impl CellType for f64 {}
 impl CellType for usize {} // for tests
 
 /// A struct to hold cell position and value
@@ -515,6 +736,42 @@ impl<T: CellType> Range<T> {
         }
     }
 
+    /// Lay out `cells` as [`Range::from_sparse`] does, converting their values with `f`
+    /// and filling the gaps with `fill`
+    pub(crate) fn from_sparse_with<U: CellType>(
+        cells: Vec<Cell<U>>,
+        fill: T,
+        mut f: impl FnMut(U) -> T,
+    ) -> Range<T> {
+        if cells.is_empty() {
+            return Range {
+                start: (0, 0),
+                end: (0, 0),
+                inner: Vec::new(),
+            };
+        }
+        let row_start = cells.first().unwrap().pos.0;
+        let row_end = cells.last().unwrap().pos.0;
+        let (col_start, col_end) = cells
+            .iter()
+            .fold((u32::MAX, 0), |(s, e), c| (s.min(c.pos.1), e.max(c.pos.1)));
+        let cols = (col_end - col_start + 1) as usize;
+        let rows = (row_end - row_start + 1) as usize;
+        let mut inner = vec![fill; cols.saturating_mul(rows)];
+        for c in cells {
+            let idx = ((c.pos.0 - row_start) as usize).saturating_mul(cols)
+                + (c.pos.1 - col_start) as usize;
+            if let Some(v) = inner.get_mut(idx) {
+                *v = f(c.val);
+            }
+        }
+        Range {
+            start: (row_start, col_start),
+            end: (row_end, col_end),
+            inner,
+        }
+    }
+
     /// Set inner value from absolute position
     ///
     /// # Remarks
@@ -624,6 +881,33 @@ impl<T: CellType> Range<T> {
         None
     }
 
+    /// Get a mutable reference to a cell value from **absolute position**.
+    ///
+    /// If the `absolute_position` is out of range, returns `None` instead of panicking.
+    /// The coordinate format is (row, column).
+    ///
+    /// For relative positions, use IndexMut trait
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range: Range<Data> = Range::new((1, 0), (5, 2));
+    /// assert_eq!(range.get_value_mut((0, 0)), None);
+    /// if let Some(v) = range.get_value_mut((1, 0)) {
+    ///     *v = Data::Int(1);
+    /// }
+    /// assert_eq!(range[(0, 0)], Data::Int(1));
+    /// ```
+    pub fn get_value_mut(&mut self, absolute_position: (u32, u32)) -> Option<&mut T> {
+        let p = absolute_position;
+        if p.0 >= self.start.0 && p.0 <= self.end.0 && p.1 >= self.start.1 && p.1 <= self.end.1 {
+            let idx = (p.0 - self.start.0) as usize * self.width() + (p.1 - self.start.1) as usize;
+            return self.inner.get_mut(idx);
+        }
+        None
+    }
+
     /// Get cell value from **relative position**.
     ///
     /// Unlike using the Index trait, this will not panic but rather yield `None` if out of range.
@@ -660,6 +944,120 @@ impl<T: CellType> Range<T> {
         }
     }
 
+    /// Get a copy of the range rows as owned vectors
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range = Range::new((0, 0), (1, 1));
+    /// range.set_value((0, 0), Data::Int(1));
+    /// range.set_value((1, 1), Data::Bool(true));
+    /// assert_eq!(
+    ///     range.to_rows_vec(),
+    ///     vec![
+    ///         vec![Data::Int(1), Data::Empty],
+    ///         vec![Data::Empty, Data::Bool(true)],
+    ///     ]
+    /// );
+    /// ```
+    pub fn to_rows_vec(&self) -> Vec<Vec<T>> {
+        self.rows().map(<[T]>::to_vec).collect()
+    }
+
+    /// Convert the range into its rows as owned vectors
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range = Range::new((0, 0), (1, 1));
+    /// range.set_value((0, 0), Data::Int(1));
+    /// range.set_value((1, 1), Data::Bool(true));
+    /// let rows: Vec<Vec<Data>> = range.into_rows_vec();
+    /// assert_eq!(rows[0], vec![Data::Int(1), Data::Empty]);
+    /// assert_eq!(rows[1], vec![Data::Empty, Data::Bool(true)]);
+    ///
+    /// let empty: Range<Data> = Range::empty();
+    /// assert!(empty.into_rows_vec().is_empty());
+    /// ```
+    pub fn into_rows_vec(self) -> Vec<Vec<T>> {
+        let width = self.width();
+        if width == 0 {
+            return Vec::new();
+        }
+        let mut rows = Vec::with_capacity(self.height());
+        let mut inner = self.inner.into_iter();
+        loop {
+            let row: Vec<T> = inner.by_ref().take(width).collect();
+            if row.is_empty() {
+                break;
+            }
+            rows.push(row);
+        }
+        rows
+    }
+
+    /// Get an iterator over batches of up to `n` consecutive rows
+    ///
+    /// The last batch has fewer rows if the height is not a multiple of `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let range: Range<Data> = Range::new((0, 0), (4, 1));
+    /// let sizes: Vec<usize> = range.row_windows(2).map(|rows| rows.len()).collect();
+    /// assert_eq!(sizes, vec![2, 2, 1]);
+    /// ```
+    pub fn row_windows(&self, n: usize) -> impl Iterator<Item = Vec<&[T]>> {
+        assert!(n != 0, "row window size must be non-zero");
+        let mut rows = self.rows();
+        std::iter::from_fn(move || {
+            let window: Vec<_> = rows.by_ref().take(n).collect();
+            if window.is_empty() {
+                None
+            } else {
+                Some(window)
+            }
+        })
+    }
+
+    /// Get an iterator over inner columns
+    ///
+    /// Each column is itself an iterator over its cells, in row order.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data, DataType};
+    ///
+    /// let mut range: Range<Data> = Range::new((0, 0), (1, 1));
+    /// range.set_value((0, 0), Data::Float(1.));
+    /// range.set_value((1, 0), Data::Float(3.));
+    /// range.set_value((0, 1), Data::Float(4.));
+    /// range.set_value((1, 1), Data::Float(2.));
+    /// let max: Vec<f64> = range
+    ///     .columns()
+    ///     .map(|col| col.filter_map(|c| c.get_float()).fold(f64::MIN, f64::max))
+    ///     .collect();
+    /// assert_eq!(max, vec![3., 4.]);
+    /// ```
+    pub fn columns(&self) -> Columns<'_, T> {
+        Columns {
+            inner: &self.inner,
+            width: if self.inner.is_empty() {
+                0
+            } else {
+                self.width()
+            },
+            col: 0,
+        }
+    }
+
     /// Get an iterator over used cells only
     pub fn used_cells(&self) -> UsedCells<'_, T> {
         UsedCells {
@@ -676,6 +1074,282 @@ impl<T: CellType> Range<T> {
         }
     }
 
+    /// Get an iterator over all cells in this range, column by column
+    ///
+    /// Items are `(row, column, value)` like for [`Range::cells`]. As cells are stored
+    /// row by row, this iterator jumps in memory: prefer `cells` when the order doesn't
+    /// matter.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range: Range<Data> = Range::new((0, 0), (1, 1));
+    /// range.set_value((0, 1), Data::Int(2));
+    /// range.set_value((1, 0), Data::Int(1));
+    /// let positions: Vec<_> = range.cells_by_column().map(|(r, c, _)| (r, c)).collect();
+    /// assert_eq!(positions, vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    /// ```
+    pub fn cells_by_column(&self) -> CellsByColumn<'_, T> {
+        CellsByColumn {
+            inner: &self.inner,
+            width: self.width(),
+            height: self.height(),
+            pos: 0,
+        }
+    }
+
+    /// Copy the last non-empty value of each given column into the empty cells below it
+    ///
+    /// This is a forward fill, useful for grouped reports where a category is only
+    /// written on its first row. Columns are relative to the range start, columns
+    /// out of the range are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range: Range<Data> = Range::new((0, 0), (2, 1));
+    /// range.set_value((0, 0), Data::String("fruit".to_string()));
+    /// range.set_value((1, 1), Data::Int(1));
+    /// range.fill_down(&[0]);
+    /// assert_eq!(range.get((1, 0)), Some(&Data::String("fruit".to_string())));
+    /// assert_eq!(range.get((2, 1)), Some(&Data::Empty));
+    /// ```
+    pub fn fill_down(&mut self, columns: &[usize]) {
+        let width = self.width();
+        let empty = T::default();
+        for &col in columns.iter().filter(|&&c| c < width) {
+            let mut last: Option<T> = None;
+            for cell in self.inner.iter_mut().skip(col).step_by(width) {
+                if *cell == empty {
+                    if let Some(v) = &last {
+                        *cell = v.clone();
+                    }
+                } else {
+                    last = Some(cell.clone());
+                }
+            }
+        }
+    }
+
+    /// Build a new range without the rows whose cells are all empty
+    ///
+    /// Unlike taking a sub-range, empty rows in the middle of the range are removed too:
+    /// the remaining rows are moved up and the range keeps its start position.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range: Range<Data> = Range::new((1, 0), (3, 0));
+    /// range.set_value((1, 0), Data::Int(1));
+    /// range.set_value((3, 0), Data::Int(3));
+    /// let range = range.drop_empty_rows();
+    /// assert_eq!(range.end(), Some((2, 0)));
+    /// assert_eq!(range.get_value((2, 0)), Some(&Data::Int(3)));
+    /// ```
+    pub fn drop_empty_rows(&self) -> Range<T> {
+        let empty = T::default();
+        let mut height = 0;
+        let mut inner = Vec::with_capacity(self.inner.len());
+        for row in self.rows().filter(|r| r.iter().any(|c| *c != empty)) {
+            inner.extend_from_slice(row);
+            height += 1;
+        }
+        if height == 0 {
+            return Range::empty();
+        }
+        Range {
+            start: self.start,
+            end: (self.start.0 + height - 1, self.end.1),
+            inner,
+        }
+    }
+
+    /// Build a new range without the columns whose cells are all empty
+    ///
+    /// Unlike taking a sub-range, empty columns in the middle of the range are removed
+    /// too: the remaining columns are moved left and the range keeps its start position.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range: Range<Data> = Range::new((0, 0), (0, 2));
+    /// range.set_value((0, 0), Data::Int(1));
+    /// range.set_value((0, 2), Data::Int(3));
+    /// let range = range.drop_empty_columns();
+    /// assert_eq!(range.end(), Some((0, 1)));
+    /// assert_eq!(range.get_value((0, 1)), Some(&Data::Int(3)));
+    /// ```
+    pub fn drop_empty_columns(&self) -> Range<T> {
+        let empty = T::default();
+        let mut keep = vec![false; self.width()];
+        for row in self.rows() {
+            for (k, c) in keep.iter_mut().zip(row) {
+                *k |= *c != empty;
+            }
+        }
+        let width = keep.iter().filter(|&&k| k).count() as u32;
+        if width == 0 {
+            return Range::empty();
+        }
+        let inner = self
+            .rows()
+            .flat_map(|row| {
+                row.iter()
+                    .zip(&keep)
+                    .filter(|(_, &k)| k)
+                    .map(|(c, _)| c.clone())
+            })
+            .collect();
+        Range {
+            start: self.start,
+            end: (self.end.0, self.start.1 + width - 1),
+            inner,
+        }
+    }
+
+    /// Get the absolute position of the first cell equal to `value`, row by row
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Data, Range};
+    ///
+    /// let mut range = Range::new((1, 1), (2, 2));
+    /// range.set_value((2, 1), Data::Int(3));
+    /// range.set_value((2, 2), Data::Int(3));
+    /// assert_eq!(range.find(&Data::Int(3)), Some((2, 1)));
+    /// assert_eq!(range.find(&Data::Int(4)), None);
+    /// ```
+    pub fn find(&self, value: &T) -> Option<(u32, u32)> {
+        let width = self.width();
+        let idx = self.inner.iter().position(|c| c == value)?;
+        Some(self.absolute_position(idx, width))
+    }
+
+    /// Get the absolute positions of all cells equal to `value`, row by row
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Data, Range};
+    ///
+    /// let mut range = Range::new((1, 1), (2, 2));
+    /// range.set_value((1, 2), Data::from("x"));
+    /// range.set_value((2, 1), Data::from("x"));
+    /// assert_eq!(range.find_all(&Data::from("x")), vec![(1, 2), (2, 1)]);
+    /// ```
+    pub fn find_all(&self, value: &T) -> Vec<(u32, u32)> {
+        let width = self.width();
+        self.inner
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| *c == value)
+            .map(|(idx, _)| self.absolute_position(idx, width))
+            .collect()
+    }
+
+    fn absolute_position(&self, idx: usize, width: usize) -> (u32, u32) {
+        let row = (idx / width) as u32;
+        let col = (idx % width) as u32;
+        (self.start.0 + row, self.start.1 + col)
+    }
+
+    /// Compare the non-empty cells of two ranges
+    ///
+    /// Unlike `==`, empty cells are ignored, so two ranges holding the same
+    /// values at the same absolute positions are equal even if their declared
+    /// start or end differ (e.g. trailing empty rows).
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut a = Range::new((0, 0), (1, 1));
+    /// a.set_value((1, 1), Data::Int(1));
+    /// let mut b = Range::new((1, 0), (5, 3));
+    /// b.set_value((1, 1), Data::Int(1));
+    /// assert!(a.content_eq(&b));
+    ///
+    /// b.set_value((4, 2), Data::Int(2));
+    /// assert!(!a.content_eq(&b));
+    /// ```
+    pub fn content_eq(&self, other: &Range<T>) -> bool {
+        self.absolute_used_cells().eq(other.absolute_used_cells())
+    }
+
+    /// Get the cells which differ between two ranges
+    ///
+    /// Returns `(row, column, old, new)` tuples in absolute positions, sorted by row
+    /// then column, where `old` is the value in `self` and `new` the value in `other`.
+    /// Empty cells are considered missing, so a cell only used in one of the ranges
+    /// yields `None` on the other side.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut old = Range::new((0, 0), (1, 1));
+    /// old.set_value((0, 0), Data::Int(1));
+    /// old.set_value((1, 1), Data::Int(2));
+    /// let mut new = old.clone();
+    /// new.set_value((1, 1), Data::Int(3));
+    ///
+    /// assert_eq!(
+    ///     old.diff(&new),
+    ///     vec![(1, 1, Some(&Data::Int(2)), Some(&Data::Int(3)))]
+    /// );
+    /// ```
+    pub fn diff<'a>(
+        &'a self,
+        other: &'a Range<T>,
+    ) -> Vec<(u32, u32, Option<&'a T>, Option<&'a T>)> {
+        let mut diff = Vec::new();
+        let mut old = self.absolute_used_cells().peekable();
+        let mut new = other.absolute_used_cells().peekable();
+        loop {
+            let (pos, old_val, new_val) = match (old.peek(), new.peek()) {
+                (None, None) => break,
+                (Some(&(r, c, v)), None) => {
+                    old.next();
+                    ((r, c), Some(v), None)
+                }
+                (None, Some(&(r, c, v))) => {
+                    new.next();
+                    ((r, c), None, Some(v))
+                }
+                (Some(&(r1, c1, v1)), Some(&(r2, c2, v2))) => match (r1, c1).cmp(&(r2, c2)) {
+                    std::cmp::Ordering::Less => {
+                        old.next();
+                        ((r1, c1), Some(v1), None)
+                    }
+                    std::cmp::Ordering::Greater => {
+                        new.next();
+                        ((r2, c2), None, Some(v2))
+                    }
+                    std::cmp::Ordering::Equal => {
+                        old.next();
+                        new.next();
+                        if v1 == v2 {
+                            continue;
+                        }
+                        ((r1, c1), Some(v1), Some(v2))
+                    }
+                },
+            };
+            diff.push((pos.0, pos.1, old_val, new_val));
+        }
+        diff
+    }
+
+    /// Iterate over used cells with their absolute position
+    fn absolute_used_cells(&self) -> impl Iterator<Item = (u32, u32, &T)> {
+        let (row, col) = self.start;
+        self.used_cells()
+            .map(move |(r, c, v)| (row + r as u32, col + c as u32, v))
+    }
+
     /// Build a `RangeDeserializer` from this configuration.
     ///
     /// # Example
@@ -814,6 +1488,278 @@ impl<T: CellType + fmt::Display> Range<T> {
             .next()
             .map(|row| row.iter().map(ToString::to_string).collect())
     }
+
+    /// Get the index, relative to the range start, of the first column whose header
+    /// is `header`
+    ///
+    /// Headers are read from the first row and trimmed, like when deserializing.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Data, Range};
+    ///
+    /// let mut range = Range::new((0, 0), (1, 1));
+    /// range.set_value((0, 0), Data::from("Name"));
+    /// range.set_value((0, 1), Data::from(" Email "));
+    /// assert_eq!(range.column_index_of("Email"), Some(1));
+    /// assert_eq!(range.column_index_of("email"), None);
+    /// assert_eq!(range.column_index_of_ignore_case("email"), Some(1));
+    /// ```
+    pub fn column_index_of(&self, header: &str) -> Option<usize> {
+        self.rows()
+            .next()?
+            .iter()
+            .position(|c| c.to_string().trim() == header)
+    }
+
+    /// Same as [`column_index_of`](Self::column_index_of), ignoring ASCII case
+    pub fn column_index_of_ignore_case(&self, header: &str) -> Option<usize> {
+        self.rows()
+            .next()?
+            .iter()
+            .position(|c| c.to_string().trim().eq_ignore_ascii_case(header))
+    }
+}
+
+impl<T: CellType + Hash> Range<T> {
+    /// Get a hash of the non-empty cells (absolute position and value)
+    ///
+    /// Values are hashed with their `Hash` implementation, whose output may change
+    /// between Rust releases: only compare hashes computed by the same build. See
+    /// `Range<Data>::content_hash` for a hash which can be persisted.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::Range;
+    ///
+    /// let mut a: Range<String> = Range::new((0, 0), (1, 1));
+    /// a.set_value((0, 0), "a".to_string());
+    /// let mut b: Range<String> = Range::new((0, 0), (5, 5));
+    /// b.set_value((0, 0), "a".to_string());
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        self.hash_used_cells(|v, hasher| v.hash(hasher))
+    }
+}
+
+impl Range<Data> {
+    /// Convert strings holding numbers written with the given separators to floats
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data, NumberSeparators};
+    ///
+    /// let mut range = Range::new((0, 0), (0, 1));
+    /// range.set_value((0, 0), Data::String("1.234,56".to_string()));
+    /// range.set_value((0, 1), Data::String("abc".to_string()));
+    /// range.parse_text_numbers(NumberSeparators::new(',', Some('.')));
+    /// assert_eq!(range.get_value((0, 0)), Some(&Data::Float(1234.56)));
+    /// assert_eq!(range.get_value((0, 1)), Some(&Data::String("abc".to_string())));
+    /// ```
+    pub fn parse_text_numbers(&mut self, separators: NumberSeparators) {
+        for v in &mut self.inner {
+            if let Data::String(s) = v {
+                if let Some(f) = separators.parse(s) {
+                    *v = Data::Float(f);
+                }
+            }
+        }
+    }
+
+    /// Deserialize the columns named in `headers` into one `Vec<T>` each
+    ///
+    /// The first row is used as header; every following row contributes one value
+    /// to each column. This is the column-wise (struct-of-arrays) counterpart of
+    /// [`Range::deserialize`], handy for statistics or plotting.
+    ///
+    /// Use `Option<T>` to accept empty cells.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeError::HeaderNotFound`] if a header is missing, or the first
+    /// error encountered while deserializing a cell.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range = Range::new((0, 0), (2, 1));
+    /// range.set_value((0, 0), Data::String("label".to_string()));
+    /// range.set_value((0, 1), Data::String("value".to_string()));
+    /// range.set_value((1, 0), Data::String("a".to_string()));
+    /// range.set_value((1, 1), Data::Float(1.5));
+    /// range.set_value((2, 0), Data::String("b".to_string()));
+    /// range.set_value((2, 1), Data::Int(2));
+    ///
+    /// let columns = range.into_columns::<f64>(&["value"]).unwrap();
+    /// assert_eq!(columns["value"], vec![1.5, 2.0]);
+    /// ```
+    pub fn into_columns<T>(&self, headers: &[&str]) -> Result<HashMap<String, Vec<T>>, DeError>
+    where
+        T: DeserializeOwned,
+    {
+        let mut rows = self.rows();
+        let header_row = rows.next().unwrap_or(&[]);
+        let (start_row, start_col) = self.start;
+        let mut columns = HashMap::with_capacity(headers.len());
+        for &header in headers {
+            let h = header.trim();
+            let col = header_row
+                .iter()
+                .position(|c| c.to_string().trim() == h)
+                .ok_or_else(|| DeError::HeaderNotFound(h.to_owned()))?;
+            let values = rows
+                .clone()
+                .enumerate()
+                .map(|(i, row)| {
+                    let pos = (start_row + 1 + i as u32, start_col + col as u32);
+                    T::deserialize(row[col].to_cell_deserializer(pos))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            columns.insert(header.to_owned(), values);
+        }
+        Ok(columns)
+    }
+
+    /// Guess which row holds the headers of a table
+    ///
+    /// Each of the first 10 rows is scored as the product of:
+    /// - its fill ratio (non-empty cells over the range width), so that titles or
+    ///   notes spanning a single cell score low,
+    /// - its ratio of distinct non-blank strings over non-empty cells, as headers
+    ///   are textual and unique,
+    /// - one plus the ratio of its string cells having a non-string value below,
+    ///   so that a textual row followed by numbers or dates is preferred.
+    ///
+    /// The first row with the best score wins. The returned index is relative to
+    /// the start of the range. Returns `None` if no row contains any string.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut range = Range::new((0, 0), (2, 1));
+    /// range.set_value((0, 0), Data::String("Sales report".to_string()));
+    /// range.set_value((1, 0), Data::String("month".to_string()));
+    /// range.set_value((1, 1), Data::String("amount".to_string()));
+    /// range.set_value((2, 0), Data::String("january".to_string()));
+    /// range.set_value((2, 1), Data::Float(12.5));
+    /// assert_eq!(range.guess_header_row(), Some(1));
+    /// ```
+    pub fn guess_header_row(&self) -> Option<usize> {
+        const MAX_CANDIDATES: usize = 10;
+
+        let width = self.width();
+        let rows = self.rows().take(MAX_CANDIDATES + 1).collect::<Vec<_>>();
+        let mut best: Option<(usize, f64)> = None;
+        for (i, row) in rows.iter().enumerate().take(MAX_CANDIDATES) {
+            let non_empty = row.iter().filter(|c| **c != Data::Empty).count();
+            let mut strings = row
+                .iter()
+                .enumerate()
+                .filter_map(|(col, c)| match c {
+                    Data::String(s) if !s.trim().is_empty() => Some((col, s.trim())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if strings.is_empty() {
+                continue;
+            }
+            let typed_below = rows.get(i + 1).map_or(0, |below| {
+                strings
+                    .iter()
+                    .filter(|(col, _)| !matches!(below[*col], Data::String(_) | Data::Empty))
+                    .count()
+            });
+            let typed_below = typed_below as f64 / strings.len() as f64;
+            strings.sort_unstable_by_key(|&(_, s)| s);
+            strings.dedup_by_key(|&mut (_, s)| s);
+
+            let fill = non_empty as f64 / width as f64;
+            let distinct = strings.len() as f64 / non_empty as f64;
+            let score = fill * distinct * (1. + typed_below);
+            if best.map_or(true, |(_, best_score)| score > best_score) {
+                best = Some((i, score));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Get a hash of the non-empty cells (absolute position and value)
+    ///
+    /// The hash is stable across runs and platforms, so it can be used to detect
+    /// whether a sheet content changed between two versions of a file.
+    ///
+    /// Floats are hashed using their bit representation: `0.0` and `-0.0` have
+    /// different hashes, and so may `NaN` values with different payloads.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Range, Data};
+    ///
+    /// let mut a = Range::new((0, 0), (1, 1));
+    /// a.set_value((1, 1), Data::Float(1.5));
+    /// let mut b = a.clone();
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    ///
+    /// b.set_value((1, 1), Data::Float(2.5));
+    /// assert_ne!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        // only write explicit bytes, `Hash` implementations are not stable
+        fn write_str(hasher: &mut utils::FnvHasher, s: &str) {
+            hasher.write_usize(s.len());
+            hasher.write(s.as_bytes());
+        }
+        self.hash_used_cells(|v, hasher| match v {
+            Data::Int(v) => {
+                hasher.write_u8(0);
+                hasher.write_i64(*v);
+            }
+            Data::Float(v) => {
+                hasher.write_u8(1);
+                hasher.write_u64(v.to_bits());
+            }
+            Data::String(v) => {
+                hasher.write_u8(2);
+                write_str(hasher, v);
+            }
+            Data::Bool(v) => {
+                hasher.write_u8(3);
+                hasher.write_u8(*v as u8);
+            }
+            Data::DateTime(v) => {
+                hasher.write_u8(4);
+                v.hash_bits(hasher);
+            }
+            Data::DateTimeIso(v) => {
+                hasher.write_u8(5);
+                write_str(hasher, v);
+            }
+            Data::DurationIso(v) => {
+                hasher.write_u8(6);
+                write_str(hasher, v);
+            }
+            Data::Error(v) => {
+                hasher.write_u8(7);
+                write_str(hasher, &v.to_string());
+            }
+            Data::Empty => hasher.write_u8(8),
+        })
+    }
+}
+
+impl<T: CellType> Range<T> {
+    fn hash_used_cells<F: Fn(&T, &mut utils::FnvHasher)>(&self, hash_value: F) -> u64 {
+        let mut hasher = utils::FnvHasher::default();
+        for (row, col, v) in self.absolute_used_cells() {
+            hasher.write_u32(row);
+            hasher.write_u32(col);
+            hash_value(v, &mut hasher);
+        }
+        hasher.finish()
+    }
 }
 
 impl<T: CellType> Index<usize> for Range<T> {
@@ -947,6 +1893,60 @@ impl<'a, T: 'a + CellType> DoubleEndedIterator for Rows<'a, T> {
 
 impl<'a, T: 'a + CellType> ExactSizeIterator for Rows<'a, T> {}
 
+/// A struct to iterate over all cells, column by column
+#[derive(Clone, Debug)]
+pub struct CellsByColumn<'a, T: CellType> {
+    inner: &'a [T],
+    width: usize,
+    height: usize,
+    pos: usize,
+}
+
+impl<'a, T: 'a + CellType> Iterator for CellsByColumn<'a, T> {
+    type Item = (usize, usize, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.inner.len() {
+            return None;
+        }
+        let row = self.pos % self.height;
+        let col = self.pos / self.height;
+        self.pos += 1;
+        Some((row, col, &self.inner[row * self.width + col]))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len() - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: 'a + CellType> ExactSizeIterator for CellsByColumn<'a, T> {}
+
+/// An iterator to read `Range` struct column by column
+#[derive(Clone, Debug)]
+pub struct Columns<'a, T: CellType> {
+    inner: &'a [T],
+    width: usize,
+    col: usize,
+}
+
+impl<'a, T: 'a + CellType> Iterator for Columns<'a, T> {
+    type Item = std::iter::StepBy<std::slice::Iter<'a, T>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.col >= self.width {
+            return None;
+        }
+        let column = self.inner[self.col..].iter().step_by(self.width);
+        self.col += 1;
+        Some(column)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.width - self.col;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: 'a + CellType> ExactSizeIterator for Columns<'a, T> {}
+
 /// Struct with the key elements of a table
 pub struct Table<T> {
     pub(crate) name: String,
@@ -6,23 +6,25 @@
 
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
-use std::io::{BufReader, Read, Seek};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::Event;
 use quick_xml::name::QName;
 use quick_xml::Reader as XmlReader;
-use zip::read::{ZipArchive, ZipFile};
+use zip::read::ZipArchive;
 use zip::result::ZipError;
 
+use crate::utils::{io_error_eq, xml_error_eq, zip_error_eq};
 use crate::vba::VbaProject;
-use crate::{Data, DataType, HeaderRow, Metadata, Range, Reader, Sheet, SheetType, SheetVisible};
+use crate::{
+    Data, DataType, HeaderRow, Metadata, NumberSeparators, Range, Reader, Sheet, SheetType,
+    SheetVisible,
+};
 use std::marker::PhantomData;
 
 const MIMETYPE: &[u8] = b"application/vnd.oasis.opendocument.spreadsheet";
 
-type OdsReader<'a> = XmlReader<BufReader<ZipFile<'a>>>;
-
 /// An enum for ods specific errors
 #[derive(Debug)]
 pub enum OdsError {
@@ -67,6 +69,7 @@ pub enum OdsError {
 #[non_exhaustive]
 struct OdsOptions {
     pub header_row: HeaderRow,
+    pub number_separators: Option<NumberSeparators>,
 }
 
 from_err!(std::io::Error, OdsError, Io);
@@ -112,8 +115,42 @@ impl std::error::Error for OdsError {
     }
 }
 
+impl PartialEq for OdsError {
+    fn eq(&self, other: &Self) -> bool {
+        use OdsError::*;
+        match (self, other) {
+            (Io(a), Io(b)) => io_error_eq(a, b),
+            (Zip(a), Zip(b)) => zip_error_eq(a, b),
+            (Xml(a), Xml(b)) => xml_error_eq(a, b),
+            (XmlAttr(a), XmlAttr(b)) => a == b,
+            (Parse(a), Parse(b)) => a == b,
+            (ParseInt(a), ParseInt(b)) => a == b,
+            (ParseFloat(a), ParseFloat(b)) => a == b,
+            (ParseBool(a), ParseBool(b)) => a == b,
+            (InvalidMime(a), InvalidMime(b)) => a == b,
+            (FileNotFound(a), FileNotFound(b)) => a == b,
+            (Eof(a), Eof(b)) => a == b,
+            (
+                Mismatch {
+                    expected: e1,
+                    found: f1,
+                },
+                Mismatch {
+                    expected: e2,
+                    found: f2,
+                },
+            ) => e1 == e2 && f1 == f2,
+            (Password, Password) => true,
+            (WorksheetNotFound(a), WorksheetNotFound(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 /// An OpenDocument Spreadsheet document parser
 ///
+/// Both zipped (`.ods`) and flat xml (`.fods`) documents are supported.
+///
 /// # Reference
 /// OASIS Open Document Format for Office Application 1.2 (ODF 1.2)
 /// http://docs.oasis-open.org/office/v1.2/OpenDocument-v1.2.pdf
@@ -127,13 +164,57 @@ pub struct Ods<RS> {
     options: OdsOptions,
 }
 
+impl<RS> Ods<RS> {
+    fn from_content(content: Content, pictures: Option<Vec<(String, Vec<u8>)>>) -> Self {
+        #[cfg(not(feature = "picture"))]
+        let _ = pictures;
+        Ods {
+            marker: PhantomData,
+            metadata: Metadata {
+                sheets: content.sheets_metadata,
+                names: content.defined_names,
+            },
+            sheets: content.sheets,
+            #[cfg(feature = "picture")]
+            pictures,
+            options: OdsOptions::default(),
+        }
+    }
+
+    /// Convert numbers stored as text, written with the given separators, to floats
+    ///
+    /// This is useful for files exported by localized applications.
+    /// By default, text cells are left as is.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, NumberSeparators, Ods, Reader};
+    ///
+    /// # let path = format!("{}/tests/issues.ods", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Ods<_> = open_workbook(path).unwrap();
+    /// workbook.with_number_separators(NumberSeparators::new(',', Some('.')));
+    /// ```
+    pub fn with_number_separators(&mut self, separators: NumberSeparators) -> &mut Self {
+        self.options.number_separators = Some(separators);
+        self
+    }
+}
+
 impl<RS> Reader<RS> for Ods<RS>
 where
     RS: Read + Seek,
 {
     type Error = OdsError;
 
-    fn new(reader: RS) -> Result<Self, OdsError> {
+    fn new(mut reader: RS) -> Result<Self, OdsError> {
+        // flat OpenDocument files (.fods) are plain xml, not zip archives
+        let mut magic = Vec::with_capacity(64);
+        (&mut reader).take(64).read_to_end(&mut magic)?;
+        reader.seek(SeekFrom::Start(0))?;
+        if is_flat_xml(&magic) {
+            return Ok(Ods::from_content(parse_flat_content(reader)?, None));
+        }
+
         let mut zip = ZipArchive::new(reader)?;
 
         // check mimetype
@@ -153,25 +234,10 @@ where
 
         #[cfg(feature = "picture")]
         let pictures = read_pictures(&mut zip)?;
+        #[cfg(not(feature = "picture"))]
+        let pictures = None;
 
-        let Content {
-            sheets,
-            sheets_metadata,
-            defined_names,
-        } = parse_content(zip)?;
-        let metadata = Metadata {
-            sheets: sheets_metadata,
-            names: defined_names,
-        };
-
-        Ok(Ods {
-            marker: PhantomData,
-            metadata,
-            sheets,
-            #[cfg(feature = "picture")]
-            pictures,
-            options: OdsOptions::default(),
-        })
+        Ok(Ods::from_content(parse_content(zip)?, pictures))
     }
 
     fn with_header_row(&mut self, header_row: HeaderRow) -> &mut Self {
@@ -191,12 +257,15 @@ where
 
     /// Read worksheet data in corresponding worksheet path
     fn worksheet_range(&mut self, name: &str) -> Result<Range<Data>, OdsError> {
-        let sheet = self
+        let mut sheet = self
             .sheets
             .get(name)
             .ok_or_else(|| OdsError::WorksheetNotFound(name.into()))?
             .0
             .to_owned();
+        if let Some(separators) = self.options.number_separators {
+            sheet.parse_text_numbers(separators);
+        }
 
         match self.options.header_row {
             HeaderRow::FirstNonEmptyRow => Ok(sheet),
@@ -214,7 +283,13 @@ where
     fn worksheets(&mut self) -> Vec<(String, Range<Data>)> {
         self.sheets
             .iter()
-            .map(|(name, (range, _formula))| (name.to_owned(), range.clone()))
+            .map(|(name, (range, _formula))| {
+                let mut range = range.clone();
+                if let Some(separators) = self.options.number_separators {
+                    range.parse_text_numbers(separators);
+                }
+                (name.to_owned(), range)
+            })
             .collect()
     }
 
@@ -238,18 +313,20 @@ struct Content {
     defined_names: Vec<(String, String)>,
 }
 
+fn xml_reader<R: BufRead>(reader: R) -> XmlReader<R> {
+    let mut r = XmlReader::from_reader(reader);
+    let config = r.config_mut();
+    config.check_end_names = false;
+    config.trim_text(false);
+    config.check_comments = false;
+    config.expand_empty_elements = true;
+    r
+}
+
 /// Check password protection
 fn check_for_password_protected<RS: Read + Seek>(zip: &mut ZipArchive<RS>) -> Result<(), OdsError> {
     let mut reader = match zip.by_name("META-INF/manifest.xml") {
-        Ok(f) => {
-            let mut r = XmlReader::from_reader(BufReader::new(f));
-            let config = r.config_mut();
-            config.check_end_names = false;
-            config.trim_text(false);
-            config.check_comments = false;
-            config.expand_empty_elements = true;
-            r
-        }
+        Ok(f) => xml_reader(BufReader::new(f)),
         Err(ZipError::FileNotFound) => return Err(OdsError::FileNotFound("META-INF/manifest.xml")),
         Err(e) => return Err(OdsError::Zip(e)),
     };
@@ -285,19 +362,54 @@ fn check_for_password_protected<RS: Read + Seek>(zip: &mut ZipArchive<RS>) -> Re
 
 /// Parses content.xml and store the result in `self.content`
 fn parse_content<RS: Read + Seek>(mut zip: ZipArchive<RS>) -> Result<Content, OdsError> {
-    let mut reader = match zip.by_name("content.xml") {
-        Ok(f) => {
-            let mut r = XmlReader::from_reader(BufReader::new(f));
-            let config = r.config_mut();
-            config.check_end_names = false;
-            config.trim_text(false);
-            config.check_comments = false;
-            config.expand_empty_elements = true;
-            r
-        }
+    let reader = match zip.by_name("content.xml") {
+        Ok(f) => xml_reader(BufReader::new(f)),
         Err(ZipError::FileNotFound) => return Err(OdsError::FileNotFound("content.xml")),
         Err(e) => return Err(OdsError::Zip(e)),
     };
+    read_content(reader)
+}
+
+/// Check if the first bytes of a file look like a flat OpenDocument (xml) file
+///
+/// Anything else is read as a zip archive.
+fn is_flat_xml(magic: &[u8]) -> bool {
+    let magic = magic.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(magic);
+    let start = magic.iter().position(|b| !b.is_ascii_whitespace());
+    let magic = &magic[start.unwrap_or(magic.len())..];
+    magic.starts_with(b"<?xml") || magic.starts_with(b"<office:document")
+}
+
+/// Parses a flat OpenDocument spreadsheet (.fods), the whole document being a
+/// single xml file with an `office:document` root element
+fn parse_flat_content<RS: Read>(reader: RS) -> Result<Content, OdsError> {
+    let mut reader = xml_reader(BufReader::new(reader));
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.name() == QName(b"office:document") => {
+                match e.try_get_attribute(b"office:mimetype")? {
+                    Some(a) if &*a.value == MIMETYPE => break,
+                    Some(a) => return Err(OdsError::InvalidMime(a.value.to_vec())),
+                    None => return Err(OdsError::InvalidMime(Vec::new())),
+                }
+            }
+            Event::Start(ref e) => {
+                return Err(OdsError::Mismatch {
+                    expected: "office:document",
+                    found: String::from_utf8_lossy(e.name().as_ref()).into_owned(),
+                })
+            }
+            Event::Eof => return Err(OdsError::Eof("office:document")),
+            _ => (),
+        }
+        buf.clear();
+    }
+    read_content(reader)
+}
+
+/// Reads the spreadsheet content, from either content.xml or a flat document
+fn read_content<R: BufRead>(mut reader: XmlReader<R>) -> Result<Content, OdsError> {
     let mut buf = Vec::with_capacity(1024);
     let mut sheets = BTreeMap::new();
     let mut defined_names = Vec::new();
@@ -376,7 +488,9 @@ fn parse_content<RS: Read + Seek>(mut zip: ZipArchive<RS>) -> Result<Content, Od
     })
 }
 
-fn read_table(reader: &mut OdsReader<'_>) -> Result<(Range<Data>, Range<String>), OdsError> {
+fn read_table<R: BufRead>(
+    reader: &mut XmlReader<R>,
+) -> Result<(Range<Data>, Range<String>), OdsError> {
     let mut cells = Vec::new();
     let mut rows_repeats = Vec::new();
     let mut formulas = Vec::new();
@@ -520,8 +634,8 @@ fn get_range<T: Default + Clone + PartialEq>(
     }
 }
 
-fn read_row(
-    reader: &mut OdsReader<'_>,
+fn read_row<R: BufRead>(
+    reader: &mut XmlReader<R>,
     row_buf: &mut Vec<u8>,
     cell_buf: &mut Vec<u8>,
     cells: &mut Vec<Data>,
@@ -569,6 +683,8 @@ fn read_row(
                 }
             }
             Ok(Event::End(ref e)) if e.name() == QName(b"table:table-row") => break,
+            // indentation of pretty printed (e.g. flat) documents
+            Ok(Event::Text(ref t)) if t.iter().all(u8::is_ascii_whitespace) => (),
             Err(e) => return Err(OdsError::Xml(e)),
             Ok(e) => {
                 return Err(OdsError::Mismatch {
@@ -584,8 +700,8 @@ fn read_row(
 /// Converts table-cell element into a `Data`
 ///
 /// ODF 1.2-19.385
-fn get_datatype(
-    reader: &mut OdsReader<'_>,
+fn get_datatype<R: BufRead>(
+    reader: &mut XmlReader<R>,
     atts: Attributes<'_>,
     buf: &mut Vec<u8>,
 ) -> Result<(Data, String, bool), OdsError> {
@@ -635,10 +751,12 @@ fn get_datatype(
         // is not present, the element content defines the value.
         let mut s = String::new();
         let mut first_paragraph = true;
+        // text outside of paragraphs is only indentation
+        let mut in_paragraph = false;
         loop {
             buf.clear();
             match reader.read_event_into(buf) {
-                Ok(Event::Text(ref e)) => {
+                Ok(Event::Text(ref e)) if in_paragraph => {
                     s.push_str(&e.unescape()?);
                 }
                 Ok(Event::End(ref e))
@@ -662,7 +780,9 @@ fn get_datatype(
                     } else {
                         s.push('\n');
                     }
+                    in_paragraph = true;
                 }
+                Ok(Event::End(ref e)) if e.name() == QName(b"text:p") => in_paragraph = false,
                 Ok(Event::Start(ref e)) if e.name() == QName(b"text:s") => {
                     let count = match e.try_get_attribute("text:c")? {
                         Some(c) => c
@@ -686,7 +806,9 @@ fn get_datatype(
     }
 }
 
-fn read_named_expressions(reader: &mut OdsReader<'_>) -> Result<Vec<(String, String)>, OdsError> {
+fn read_named_expressions<R: BufRead>(
+    reader: &mut XmlReader<R>,
+) -> Result<Vec<(String, String)>, OdsError> {
     let mut defined_names = Vec::new();
     let mut buf = Vec::with_capacity(512);
     loop {
@@ -722,6 +844,7 @@ fn read_named_expressions(reader: &mut OdsReader<'_>) -> Result<Vec<(String, Str
                 if e.name() == QName(b"table:named-range")
                     || e.name() == QName(b"table:named-expression") => {}
             Ok(Event::End(ref e)) if e.name() == QName(b"table:named-expressions") => break,
+            Ok(Event::Text(ref t)) if t.iter().all(u8::is_ascii_whitespace) => (),
             Err(e) => return Err(OdsError::Xml(e)),
             Ok(e) => {
                 return Err(OdsError::Mismatch {
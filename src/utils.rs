@@ -10,6 +10,33 @@ macro_rules! from_err {
     };
 }
 
+/// Compares io errors by kind
+pub(crate) fn io_error_eq(a: &std::io::Error, b: &std::io::Error) -> bool {
+    a.kind() == b.kind()
+}
+
+/// Compares zip errors by variant, io errors by kind
+pub(crate) fn zip_error_eq(a: &zip::result::ZipError, b: &zip::result::ZipError) -> bool {
+    use zip::result::ZipError;
+    match (a, b) {
+        (ZipError::Io(a), ZipError::Io(b)) => io_error_eq(a, b),
+        (ZipError::InvalidArchive(a), ZipError::InvalidArchive(b)) => a == b,
+        (ZipError::UnsupportedArchive(a), ZipError::UnsupportedArchive(b)) => a == b,
+        (ZipError::FileNotFound, ZipError::FileNotFound) => true,
+        (ZipError::InvalidPassword, ZipError::InvalidPassword) => true,
+        _ => false,
+    }
+}
+
+/// Compares xml errors by message, io errors by kind
+pub(crate) fn xml_error_eq(a: &quick_xml::Error, b: &quick_xml::Error) -> bool {
+    match (a, b) {
+        (quick_xml::Error::Io(a), quick_xml::Error::Io(b)) => io_error_eq(a, b),
+        (quick_xml::Error::Io(_), _) | (_, quick_xml::Error::Io(_)) => false,
+        _ => a.to_string() == b.to_string(),
+    }
+}
+
 /// Converts a &[u8] into an iterator of `u32`s
 pub fn to_u32(s: &[u8]) -> impl ExactSizeIterator<Item = u32> + '_ {
     assert_eq!(s.len() % 4, 0);
@@ -68,6 +95,50 @@ pub fn push_column(mut col: u32, buf: &mut String) {
     }
 }
 
+/// A FNV-1a hasher, used where hashes must be stable across runs and platforms
+///
+/// Integers are always written in little endian, with `usize` widened to 64 bits.
+pub struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl std::hash::Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write(&i.to_le_bytes());
+    }
+}
+
 pub const FTAB_LEN: usize = 485;
 
 /* [MS-XLS] 2.5.198.17 */
@@ -11,7 +11,7 @@ use byteorder::{LittleEndian, ReadBytesExt};
 use log::{debug, log_enabled, warn, Level};
 
 use crate::cfb::{Cfb, XlsEncoding};
-use crate::utils::read_u16;
+use crate::utils::{io_error_eq, read_u16};
 
 /// A VBA specific error enum
 #[derive(Debug)]
@@ -72,6 +72,30 @@ impl std::error::Error for VbaError {
     }
 }
 
+impl PartialEq for VbaError {
+    fn eq(&self, other: &Self) -> bool {
+        use VbaError::*;
+        match (self, other) {
+            (Cfb(a), Cfb(b)) => a == b,
+            (Io(a), Io(b)) => io_error_eq(a, b),
+            (ModuleNotFound(a), ModuleNotFound(b)) => a == b,
+            (Unknown { typ: t1, val: v1 }, Unknown { typ: t2, val: v2 }) => t1 == t2 && v1 == v2,
+            (LibId, LibId) => true,
+            (
+                InvalidRecordId {
+                    expected: e1,
+                    found: f1,
+                },
+                InvalidRecordId {
+                    expected: e2,
+                    found: f2,
+                },
+            ) => e1 == e2 && f1 == f2,
+            _ => false,
+        }
+    }
+}
+
 /// A struct for managing VBA reading
 #[allow(dead_code)]
 #[derive(Clone)]
@@ -14,11 +14,11 @@ use crate::formats::{
 };
 #[cfg(feature = "picture")]
 use crate::utils::read_usize;
-use crate::utils::{push_column, read_f64, read_i16, read_i32, read_u16, read_u32};
+use crate::utils::{io_error_eq, push_column, read_f64, read_i16, read_i32, read_u16, read_u32};
 use crate::vba::VbaProject;
 use crate::{
-    Cell, CellErrorType, Data, Dimensions, HeaderRow, Metadata, Range, Reader, Sheet, SheetType,
-    SheetVisible,
+    Cell, CellErrorType, Data, Dimensions, HeaderRow, Metadata, NumberSeparators, Range, Reader,
+    Sheet, SheetType, SheetVisible,
 };
 
 #[derive(Debug)]
@@ -124,6 +124,44 @@ impl std::error::Error for XlsError {
     }
 }
 
+impl PartialEq for XlsError {
+    fn eq(&self, other: &Self) -> bool {
+        use XlsError::*;
+        match (self, other) {
+            (Io(a), Io(b)) => io_error_eq(a, b),
+            (Cfb(a), Cfb(b)) => a == b,
+            (Vba(a), Vba(b)) => a == b,
+            (StackLen, StackLen) => true,
+            (Unrecognized { typ: t1, val: v1 }, Unrecognized { typ: t2, val: v2 }) => {
+                t1 == t2 && v1 == v2
+            }
+            (Password, Password) => true,
+            (
+                Len {
+                    expected: e1,
+                    found: f1,
+                    typ: t1,
+                },
+                Len {
+                    expected: e2,
+                    found: f2,
+                    typ: t2,
+                },
+            ) => e1 == e2 && f1 == f2 && t1 == t2,
+            (ContinueRecordTooShort, ContinueRecordTooShort) => true,
+            (EoStream(a), EoStream(b)) => a == b,
+            (InvalidFormula { stack_size: a }, InvalidFormula { stack_size: b }) => a == b,
+            (IfTab(a), IfTab(b)) => a == b,
+            (Etpg(a), Etpg(b)) => a == b,
+            (NoVba, NoVba) => true,
+            #[cfg(feature = "picture")]
+            (Art(a), Art(b)) => a == b,
+            (WorksheetNotFound(a), WorksheetNotFound(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 /// Options to perform specialized parsing.
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
@@ -139,6 +177,10 @@ pub struct XlsOptions {
     pub force_codepage: Option<u16>,
     /// Row to use as header
     pub header_row: HeaderRow,
+    /// Separators used to convert numbers stored as text to floats.
+    ///
+    /// By default, text cells are left as is.
+    pub number_separators: Option<NumberSeparators>,
 }
 
 struct SheetData {
@@ -213,6 +255,15 @@ impl<RS: Read + Seek> Xls<RS> {
         Ok(xls)
     }
 
+    /// Convert numbers stored as text, written with the given separators, to floats
+    ///
+    /// This is useful for files exported by localized applications.
+    /// By default, text cells are left as is.
+    pub fn with_number_separators(&mut self, separators: NumberSeparators) -> &mut Self {
+        self.options.number_separators = Some(separators);
+        self
+    }
+
     /// Gets the worksheet merge cell dimensions
     pub fn worksheet_merge_cells(&self, name: &str) -> Option<Vec<Dimensions>> {
         self.sheets.get(name).map(|r| r.merge_cells.clone())
@@ -249,11 +300,14 @@ impl<RS: Read + Seek> Reader<RS> for Xls<RS> {
     }
 
     fn worksheet_range(&mut self, name: &str) -> Result<Range<Data>, XlsError> {
-        let sheet = self
+        let mut sheet = self
             .sheets
             .get(name)
             .map(|r| r.range.clone())
             .ok_or_else(|| XlsError::WorksheetNotFound(name.into()))?;
+        if let Some(separators) = self.options.number_separators {
+            sheet.parse_text_numbers(separators);
+        }
 
         match self.options.header_row {
             HeaderRow::FirstNonEmptyRow => Ok(sheet),
@@ -271,7 +325,13 @@ impl<RS: Read + Seek> Reader<RS> for Xls<RS> {
     fn worksheets(&mut self) -> Vec<(String, Range<Data>)> {
         self.sheets
             .iter()
-            .map(|(name, sheet)| (name.to_owned(), sheet.range.clone()))
+            .map(|(name, sheet)| {
+                let mut range = sheet.range.clone();
+                if let Some(separators) = self.options.number_separators {
+                    range.parse_text_numbers(separators);
+                }
+                (name.to_owned(), range)
+            })
             .collect()
     }
 
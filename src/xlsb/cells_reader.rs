@@ -1,3 +1,7 @@
+use std::borrow::Cow;
+
+use log::warn;
+
 use crate::{
     datatype::DataRef,
     formats::{format_excel_f64_ref, CellFormat},
@@ -5,13 +9,13 @@ use crate::{
     Cell, CellErrorType, Dimensions, XlsbError,
 };
 
-use super::{cell_format, parse_formula, wide_str, RecordIter};
+use super::{cell_format, parse_formula, split_formula, wide_str, RecordIter, SharedStrings};
 
 /// A cells reader for xlsb files
 pub struct XlsbCellsReader<'a> {
     iter: RecordIter<'a>,
     formats: &'a [CellFormat],
-    strings: &'a [String],
+    strings: &'a SharedStrings,
     extern_sheets: &'a [String],
     metadata_names: &'a [(String, String)],
     typ: u16,
@@ -25,22 +29,13 @@ impl<'a> XlsbCellsReader<'a> {
     pub(crate) fn new(
         mut iter: RecordIter<'a>,
         formats: &'a [CellFormat],
-        strings: &'a [String],
+        strings: &'a SharedStrings,
         extern_sheets: &'a [String],
         metadata_names: &'a [(String, String)],
         is_1904: bool,
     ) -> Result<Self, XlsbError> {
         let mut buf = Vec::with_capacity(1024);
-        // BrtWsDim
-        let _ = iter.next_skip_blocks(
-            0x0094,
-            &[
-                (0x0081, None), // BrtBeginSheet
-                (0x0093, None), // BrtWsProp
-            ],
-            &mut buf,
-        )?;
-        let dimensions = parse_dimensions(&buf[..16]);
+        let dimensions = read_dimensions(&mut iter, &mut buf)?;
 
         // BrtBeginSheetData
         let _ = iter.next_skip_blocks(
@@ -116,6 +111,8 @@ impl<'a> XlsbCellsReader<'a> {
                         0x24 => CellErrorType::Num,
                         0x2A => CellErrorType::NA,
                         0x2B => CellErrorType::GettingData,
+                        0x2C => CellErrorType::Spill,
+                        0x2D => CellErrorType::Calc,
                         c => return Err(XlsbError::CellError(c)),
                     };
                     // BrtCellError
@@ -130,7 +127,17 @@ impl<'a> XlsbCellsReader<'a> {
                 0x0007 => {
                     // BrtCellIsst
                     let isst = read_usize(&self.buf[8..12]);
-                    DataRef::SharedString(&self.strings[isst])
+                    match self.strings.get(isst) {
+                        Some(Cow::Borrowed(s)) => DataRef::SharedString(s),
+                        Some(Cow::Owned(s)) => DataRef::String(s),
+                        None => {
+                            warn!(
+                                "shared string index {isst} out of range ({} strings)",
+                                self.strings.len()
+                            );
+                            DataRef::Error(CellErrorType::GettingData)
+                        }
+                    }
                 }
                 0x0000 => {
                     // BrtRowHdr
@@ -159,24 +166,18 @@ impl<'a> XlsbCellsReader<'a> {
                 0x0008 => {
                     // BrtFmlaString
                     let cch = read_u32(&self.buf[8..]) as usize;
-                    let formula = &self.buf[14 + cch * 2..];
-                    let cce = read_u32(formula) as usize;
-                    let rgce = &formula[4..4 + cce];
-                    parse_formula(rgce, self.extern_sheets, self.metadata_names)?
+                    let (rgce, rgcb) = split_formula(&self.buf[14 + cch * 2..]);
+                    parse_formula(rgce, rgcb, self.extern_sheets, self.metadata_names)?
                 }
                 0x0009 => {
                     // BrtFmlaNum
-                    let formula = &self.buf[18..];
-                    let cce = read_u32(formula) as usize;
-                    let rgce = &formula[4..4 + cce];
-                    parse_formula(rgce, self.extern_sheets, self.metadata_names)?
+                    let (rgce, rgcb) = split_formula(&self.buf[18..]);
+                    parse_formula(rgce, rgcb, self.extern_sheets, self.metadata_names)?
                 }
                 0x000A | 0x000B => {
                     // BrtFmlaBool | BrtFmlaError
-                    let formula = &self.buf[11..];
-                    let cce = read_u32(formula) as usize;
-                    let rgce = &formula[4..4 + cce];
-                    parse_formula(rgce, self.extern_sheets, self.metadata_names)?
+                    let (rgce, rgcb) = split_formula(&self.buf[11..]);
+                    parse_formula(rgce, rgcb, self.extern_sheets, self.metadata_names)?
                 }
                 0x0000 => {
                     // BrtRowHdr
@@ -196,6 +197,23 @@ impl<'a> XlsbCellsReader<'a> {
     }
 }
 
+/// Reads the BrtWsDim record, which comes before any cell data
+pub(crate) fn read_dimensions(
+    iter: &mut RecordIter<'_>,
+    buf: &mut Vec<u8>,
+) -> Result<Dimensions, XlsbError> {
+    // BrtWsDim
+    let _ = iter.next_skip_blocks(
+        0x0094,
+        &[
+            (0x0081, None), // BrtBeginSheet
+            (0x0093, None), // BrtWsProp
+        ],
+        buf,
+    )?;
+    Ok(parse_dimensions(&buf[..16]))
+}
+
 fn parse_dimensions(buf: &[u8]) -> Dimensions {
     Dimensions {
         start: (read_u32(&buf[0..4]), read_u32(&buf[8..12])),
@@ -2,11 +2,13 @@ mod cells_reader;
 
 pub use cells_reader::XlsbCellsReader;
 
+use cells_reader::read_dimensions;
+
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::io::{BufReader, Read, Seek};
 
-use log::debug;
+use log::{debug, warn};
 
 use encoding_rs::UTF_16LE;
 use quick_xml::events::attributes::Attribute;
@@ -18,10 +20,14 @@ use zip::result::ZipError;
 
 use crate::datatype::DataRef;
 use crate::formats::{builtin_format_by_code, detect_custom_number_format, CellFormat};
-use crate::utils::{push_column, read_f64, read_i32, read_u16, read_u32, read_usize};
+use crate::utils::{
+    io_error_eq, push_column, read_f64, read_i32, read_u16, read_u32, read_usize, xml_error_eq,
+    zip_error_eq,
+};
 use crate::vba::VbaProject;
 use crate::{
-    Cell, Data, HeaderRow, Metadata, Range, Reader, ReaderRef, Sheet, SheetType, SheetVisible,
+    Cell, Data, Dimensions, HeaderRow, Metadata, Range, Reader, ReaderRef, Sheet, SheetType,
+    SheetVisible,
 };
 
 /// A Xlsb specific error
@@ -130,11 +136,125 @@ impl std::error::Error for XlsbError {
     }
 }
 
-/// Xlsb reader options
-#[derive(Debug, Default)]
+impl PartialEq for XlsbError {
+    fn eq(&self, other: &Self) -> bool {
+        use XlsbError::*;
+        match (self, other) {
+            (Io(a), Io(b)) => io_error_eq(a, b),
+            (Zip(a), Zip(b)) => zip_error_eq(a, b),
+            (Xml(a), Xml(b)) => xml_error_eq(a, b),
+            (XmlAttr(a), XmlAttr(b)) => a == b,
+            (Vba(a), Vba(b)) => a == b,
+            (
+                Mismatch {
+                    expected: e1,
+                    found: f1,
+                },
+                Mismatch {
+                    expected: e2,
+                    found: f2,
+                },
+            ) => e1 == e2 && f1 == f2,
+            (FileNotFound(a), FileNotFound(b)) => a == b,
+            (StackLen, StackLen) => true,
+            (UnsupportedType(a), UnsupportedType(b)) => a == b,
+            (Etpg(a), Etpg(b)) => a == b,
+            (IfTab(a), IfTab(b)) => a == b,
+            (BErr(a), BErr(b)) => a == b,
+            (Ptg(a), Ptg(b)) => a == b,
+            (CellError(a), CellError(b)) => a == b,
+            (
+                WideStr {
+                    ws_len: w1,
+                    buf_len: b1,
+                },
+                WideStr {
+                    ws_len: w2,
+                    buf_len: b2,
+                },
+            ) => w1 == w2 && b1 == b2,
+            (Unrecognized { typ: t1, val: v1 }, Unrecognized { typ: t2, val: v2 }) => {
+                t1 == t2 && v1 == v2
+            }
+            (Password, Password) => true,
+            (WorksheetNotFound(a), WorksheetNotFound(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Xlsb reader options, see [`Xlsb::new_with_options`]
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-struct XlsbOptions {
+pub struct XlsbOptions {
+    /// Row to use as header
     pub header_row: HeaderRow,
+    /// Keep the shared strings undecoded and decode them on each lookup
+    ///
+    /// When opening the workbook, the shared strings table is decompressed once and
+    /// only indexed: the raw UTF-16 content of the strings is kept along with their
+    /// byte offsets, and each shared string cell decodes (and allocates) its own string.
+    /// Strings cannot be read on demand from the archive itself, as its deflate stream
+    /// doesn't support random access.
+    ///
+    /// Opening workbooks with huge string tables is faster, as no string is decoded
+    /// upfront, but reading cells is slower. Memory usage is lower for mostly non latin
+    /// text, whose UTF-16 encoding is smaller than UTF-8, and up to twice higher for
+    /// ASCII text. Defaults to `false`: all strings are decoded when the workbook is
+    /// opened.
+    pub lazy_shared_strings: bool,
+}
+
+/// Shared strings table
+///
+/// By default, all strings are decoded once, when the workbook is opened, and stored
+/// back to back in a single buffer, only their end offsets are kept aside. Compared to
+/// one `String` per item, this avoids an allocation (and its bookkeeping) per string,
+/// which dominates memory usage for workbooks with millions of short strings.
+///
+/// In lazy mode (`XlsbOptions::lazy_shared_strings`), the buffer holds the raw UTF-16
+/// content of the strings instead, which is decoded on each lookup. The zip entry itself
+/// cannot be read on demand: its deflate stream doesn't support random access.
+#[derive(Debug, Default)]
+pub(crate) struct SharedStrings {
+    buf: String,
+    raw: Vec<u8>,
+    ends: Vec<usize>,
+    lazy: bool,
+}
+
+impl SharedStrings {
+    fn push(&mut self, s: &str) {
+        self.buf.push_str(s);
+        self.ends.push(self.buf.len());
+    }
+
+    /// Push the UTF-16LE content of a string, decoded on lookup
+    fn push_raw(&mut self, utf16: &[u8]) {
+        self.raw.extend_from_slice(utf16);
+        self.ends.push(self.raw.len());
+    }
+
+    /// Get the string at index `i`, only borrowed if the table is not lazy
+    pub(crate) fn get(&self, i: usize) -> Option<Cow<'_, str>> {
+        let end = *self.ends.get(i)?;
+        let start = match i {
+            0 => 0,
+            i => self.ends[i - 1],
+        };
+        if self.lazy {
+            Some(Cow::Owned(
+                UTF_16LE.decode(&self.raw[start..end]).0.into_owned(),
+            ))
+        } else {
+            Some(Cow::Borrowed(&self.buf[start..end]))
+        }
+    }
+
+    /// Number of strings in the table
+    pub(crate) fn len(&self) -> usize {
+        self.ends.len()
+    }
 }
 
 /// A Xlsb reader
@@ -142,7 +262,7 @@ pub struct Xlsb<RS> {
     zip: ZipArchive<RS>,
     extern_sheets: Vec<String>,
     sheets: Vec<(String, String)>,
-    strings: Vec<String>,
+    strings: SharedStrings,
     /// Cell (number) formats
     formats: Vec<CellFormat>,
     is_1904: bool,
@@ -239,6 +359,7 @@ impl<RS: Read + Seek> Xlsb<RS> {
                         match builtin_format_by_code(fmt_code) {
                             CellFormat::DateTime => self.formats.push(CellFormat::DateTime),
                             CellFormat::TimeDelta => self.formats.push(CellFormat::TimeDelta),
+                            CellFormat::Text => self.formats.push(CellFormat::Text),
                             CellFormat::Other => {
                                 self.formats.push(
                                     number_formats
@@ -252,7 +373,11 @@ impl<RS: Read + Seek> Xlsb<RS> {
                     // BrtBeginCellXFs is always present and always after BrtBeginFmts
                     break;
                 }
-                _ => (),
+                _ => {
+                    // skip the record content (e.g. BrtXF of BrtBeginCellStyleXFs), it must
+                    // not be mistaken for record types
+                    let _ = iter.fill_buffer(&mut buf)?;
+                }
             }
             buf.clear();
         }
@@ -270,6 +395,10 @@ impl<RS: Read + Seek> Xlsb<RS> {
 
         let _ = iter.next_skip_blocks(0x009F, &[], &mut buf)?; // BrtBeginSst
         let len = read_usize(&buf[4..8]);
+        if len < 1_000_000 {
+            self.strings.ends.reserve(len);
+        }
+        self.strings.lazy = self.options.lazy_shared_strings;
 
         // BrtSSTItems
         for _ in 0..len {
@@ -280,7 +409,13 @@ impl<RS: Read + Seek> Xlsb<RS> {
                 ],
                 &mut buf,
             )?; // BrtSSTItem
-            self.strings.push(wide_str(&buf[1..], &mut 0)?.into_owned());
+            if self.strings.lazy {
+                let mut str_len = 0;
+                wide_str_len(&buf[1..], &mut str_len)?;
+                self.strings.push_raw(&buf[5..1 + str_len]);
+            } else {
+                self.strings.push(&wide_str(&buf[1..], &mut 0)?);
+            }
         }
         Ok(())
     }
@@ -380,9 +515,17 @@ impl<RS: Read + Seek> Xlsb<RS> {
                     let len = iter.fill_buffer(&mut buf)?;
                     let mut str_len = 0;
                     let name = wide_str(&buf[9..len], &mut str_len)?.into_owned();
-                    let rgce_len = read_u32(&buf[9 + str_len..]) as usize;
-                    let rgce = &buf[13 + str_len..13 + str_len + rgce_len];
-                    let formula = parse_formula(rgce, &self.extern_sheets, &defined_names)?;
+                    let (rgce, rgcb) = split_formula(&buf[9 + str_len..len]);
+                    let formula =
+                        match parse_formula(rgce, rgcb, &self.extern_sheets, &defined_names) {
+                            Ok(formula) => formula,
+                            Err(e @ (XlsbError::Ptg(_) | XlsbError::Etpg(_))) => {
+                                // keep the workbook readable when a name cannot be parsed
+                                warn!("cannot parse defined name '{name}' formula: {e}");
+                                String::new()
+                            }
+                            Err(e) => return Err(e),
+                        };
                     defined_names.push((name, formula));
                 }
                 0x009D | 0x0225 | 0x018D | 0x0180 | 0x009A | 0x0252 | 0x0229 | 0x009B | 0x0084 => {
@@ -395,6 +538,59 @@ impl<RS: Read + Seek> Xlsb<RS> {
         }
     }
 
+    /// Consume the workbook and return the underlying reader
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook_from_rs, Xlsb};
+    /// use std::io::Cursor;
+    ///
+    /// # let path = format!("{}/tests/issues.xlsb", env!("CARGO_MANIFEST_DIR"));
+    /// let bytes = std::fs::read(path).unwrap();
+    /// let workbook: Xlsb<_> = open_workbook_from_rs(Cursor::new(bytes.clone())).unwrap();
+    /// assert_eq!(workbook.into_inner().into_inner(), bytes);
+    /// ```
+    pub fn into_inner(self) -> RS {
+        self.zip.into_inner()
+    }
+
+    /// Get the index of a sheet, in workbook order
+    ///
+    /// This is the index the extern sheets table refers to.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsb};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsb", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsb<_> = open_workbook(path).unwrap();
+    /// assert_eq!(workbook.sheet_index("issue2"), Some(1));
+    /// assert_eq!(workbook.sheet_index("missing"), None);
+    /// ```
+    pub fn sheet_index(&self, name: &str) -> Option<usize> {
+        self.sheets.iter().position(|(n, _)| n == name)
+    }
+
+    /// Get the sheet name of an extern sheets table entry
+    ///
+    /// Formulas refer to other sheets by their index (`ixti`) in this table. Entries
+    /// which are not a sheet of the workbook have a placeholder name, such as
+    /// `#ThisWorkbook`.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsb};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsb", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsb<_> = open_workbook(path).unwrap();
+    /// let name = workbook.extern_sheet_name(1).unwrap();
+    /// assert_eq!(name, "Sheet1");
+    /// assert_eq!(workbook.sheet_index(name), Some(2));
+    /// ```
+    pub fn extern_sheet_name(&self, ixti: usize) -> Option<&str> {
+        self.extern_sheets.get(ixti).map(String::as_str)
+    }
+
     /// Get a cells reader for a given worksheet
     pub fn worksheet_cells_reader<'a>(
         &'a mut self,
@@ -415,6 +611,30 @@ impl<RS: Read + Seek> Xlsb<RS> {
         )
     }
 
+    /// Get the declared dimensions of a worksheet
+    ///
+    /// Only the `BrtWsDim` record at the start of the sheet stream is read, no cell
+    /// is parsed. The dimensions are the ones written by the producing application
+    /// and may be larger than the actual used range.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Dimensions, Xlsb};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsb", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsb<_> = open_workbook(path).unwrap();
+    /// let dimensions = workbook.worksheet_dimensions("datatypes").unwrap();
+    /// assert_eq!((dimensions.start, dimensions.end), ((0, 0), (5, 0)));
+    /// ```
+    pub fn worksheet_dimensions(&mut self, name: &str) -> Result<Dimensions, XlsbError> {
+        let path = match self.sheets.iter().find(|&(n, _)| n == name) {
+            Some((_, path)) => path.clone(),
+            None => return Err(XlsbError::WorksheetNotFound(name.into())),
+        };
+        let mut iter = RecordIter::from_zip(&mut self.zip, &path)?;
+        read_dimensions(&mut iter, &mut Vec::with_capacity(16))
+    }
+
     #[cfg(feature = "picture")]
     fn read_pictures(&mut self) -> Result<(), XlsbError> {
         let mut pics = Vec::new();
@@ -444,23 +664,35 @@ impl<RS: Read + Seek> Xlsb<RS> {
     }
 }
 
-impl<RS: Read + Seek> Reader<RS> for Xlsb<RS> {
-    type Error = XlsbError;
-
-    fn new(mut reader: RS) -> Result<Self, XlsbError> {
+impl<RS: Read + Seek> Xlsb<RS> {
+    /// Creates a new instance using `XlsbOptions`
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Reader, Xlsb, XlsbOptions};
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsb", env!("CARGO_MANIFEST_DIR"));
+    /// let mut options = XlsbOptions::default();
+    /// options.lazy_shared_strings = true;
+    /// let reader = BufReader::new(File::open(path).unwrap());
+    /// let mut workbook = Xlsb::new_with_options(reader, options).unwrap();
+    /// assert!(workbook.worksheet_range("issue2").is_ok());
+    /// ```
+    pub fn new_with_options(mut reader: RS, options: XlsbOptions) -> Result<Self, XlsbError> {
         check_for_password_protected(&mut reader)?;
 
         let mut xlsb = Xlsb {
             zip: ZipArchive::new(reader)?,
             sheets: Vec::new(),
-            strings: Vec::new(),
+            strings: SharedStrings::default(),
             extern_sheets: Vec::new(),
             formats: Vec::new(),
             is_1904: false,
             metadata: Metadata::default(),
             #[cfg(feature = "picture")]
             pictures: None,
-            options: XlsbOptions::default(),
+            options,
         };
         xlsb.read_shared_strings()?;
         xlsb.read_styles()?;
@@ -471,6 +703,14 @@ impl<RS: Read + Seek> Reader<RS> for Xlsb<RS> {
 
         Ok(xlsb)
     }
+}
+
+impl<RS: Read + Seek> Reader<RS> for Xlsb<RS> {
+    type Error = XlsbError;
+
+    fn new(reader: RS) -> Result<Self, XlsbError> {
+        Self::new_with_options(reader, XlsbOptions::default())
+    }
 
     fn with_header_row(&mut self, header_row: HeaderRow) -> &mut Self {
         self.options.header_row = header_row;
@@ -580,7 +820,7 @@ impl<RS: Read + Seek> ReaderRef<RS> for Xlsb<RS> {
 
                 // If `header_row` is set and the first non-empty cell is not at the `header_row`, we add
                 // an empty cell at the beginning with row `header_row` and same column as the first non-empty cell.
-                if cells.first().map_or(false, |c| c.pos.0 != header_row_idx) {
+                if cells.first().is_some_and(|c| c.pos.0 != header_row_idx) {
                     cells.insert(
                         0,
                         Cell {
@@ -677,6 +917,13 @@ impl<'a> RecordIter<'a> {
 }
 
 fn wide_str<'a>(buf: &'a [u8], str_len: &mut usize) -> Result<Cow<'a, str>, XlsbError> {
+    wide_str_len(buf, str_len)?;
+    let s = &buf[4..*str_len];
+    Ok(UTF_16LE.decode(s).0)
+}
+
+/// Check the length of a XLWideString, without decoding it
+fn wide_str_len(buf: &[u8], str_len: &mut usize) -> Result<(), XlsbError> {
     let len = read_u32(buf) as usize;
     if buf.len() < 4 + len * 2 {
         return Err(XlsbError::WideStr {
@@ -685,8 +932,23 @@ fn wide_str<'a>(buf: &'a [u8], str_len: &mut usize) -> Result<Cow<'a, str>, Xlsb
         });
     }
     *str_len = 4 + len * 2;
-    let s = &buf[4..*str_len];
-    Ok(UTF_16LE.decode(s).0)
+    Ok(())
+}
+
+/// Splits a CellParsedFormula or NameParsedFormula into its `rgce` and `rgcb` parts
+///
+/// [MS-XLSB 2.5.97.1]
+fn split_formula(buf: &[u8]) -> (&[u8], &[u8]) {
+    let cce = read_u32(buf) as usize;
+    let rgce = &buf[4..4 + cce];
+    let rgcb = match buf.get(4 + cce..) {
+        Some(extra) if extra.len() >= 4 => {
+            let cb = read_u32(extra) as usize;
+            &extra[4..extra.len().min(4 + cb)]
+        }
+        _ => &[],
+    };
+    (rgce, rgcb)
 }
 
 /// Formula parsing
@@ -696,7 +958,18 @@ fn wide_str<'a>(buf: &'a [u8], str_len: &mut usize) -> Result<Cow<'a, str>, Xlsb
 ///
 /// See Ptg [2.5.97.16]
 fn parse_formula(
+    rgce: &[u8],
+    mut rgcb: &[u8],
+    sheets: &[String],
+    names: &[(String, String)],
+) -> Result<String, XlsbError> {
+    parse_formula_with_extra(rgce, &mut rgcb, sheets, names)
+}
+
+/// Formula parsing, consuming the extra data (`rgcb`) in the order ptgs reference it
+fn parse_formula_with_extra(
     mut rgce: &[u8],
+    rgcb: &mut &[u8],
     sheets: &[String],
     names: &[(String, String)],
 ) -> Result<String, XlsbError> {
@@ -858,6 +1131,8 @@ fn parse_formula(
                     0x24 => formula.push_str("#NUM!"),
                     0x2A => formula.push_str("#N/A"),
                     0x2B => formula.push_str("#GETTING_DATA"),
+                    0x2C => formula.push_str("#SPILL!"),
+                    0x2D => formula.push_str("#CALC!"),
                     e => return Err(XlsbError::BErr(e)),
                 }
             }
@@ -877,9 +1152,10 @@ fn parse_formula(
                 rgce = &rgce[8..];
             }
             0x20 | 0x40 | 0x60 => {
-                // PtgArray: ignore
+                // PtgArray: values are stored in rgcb as a PtgExtraArray
                 stack.push(formula.len());
                 rgce = &rgce[14..];
+                parse_extra_array(rgcb, &mut formula)?;
             }
             0x21 | 0x22 | 0x41 | 0x42 | 0x61 | 0x62 => {
                 let (iftab, argc) = match ptg {
@@ -972,10 +1248,26 @@ fn parse_formula(
                 formula.push_str("#REF!");
                 rgce = &rgce[12..];
             }
+            0x26 | 0x46 | 0x66 => {
+                // PtgMemArea: the cached areas are stored in rgcb as a PtgExtraMem,
+                // the reference itself follows as a regular subexpression
+                rgce = &rgce[6..];
+                let count = rgcb.get(..4).map_or(0, read_u32) as usize;
+                *rgcb = rgcb
+                    .get(count.saturating_mul(16).saturating_add(4)..)
+                    .ok_or_else(|| XlsbError::Unrecognized {
+                        typ: "PtgExtraMem",
+                        val: format!("{count} areas in {} bytes", rgcb.len()),
+                    })?;
+            }
+            0x27 | 0x47 | 0x67 | 0x28 | 0x48 | 0x68 => {
+                // PtgMemErr | PtgMemNoMem: transparent wrappers around the following subexpression
+                rgce = &rgce[6..];
+            }
             0x29 | 0x49 | 0x69 => {
                 let cce = read_u16(rgce) as usize;
                 rgce = &rgce[2..];
-                let f = parse_formula(&rgce[..cce], sheets, names)?;
+                let f = parse_formula_with_extra(&rgce[..cce], rgcb, sheets, names)?;
                 stack.push(formula.len());
                 formula.push_str(&f);
                 rgce = &rgce[cce..];
@@ -997,11 +1289,98 @@ fn parse_formula(
     }
 }
 
+/// Reads an array constant and pushes it to the formula, e.g. `{1,2;3,4}`
+///
+/// See PtgExtraArray [MS-XLSB 2.5.97.90] and SerAr [MS-XLSB 2.5.97.94]
+fn parse_extra_array(rgcb: &mut &[u8], formula: &mut String) -> Result<(), XlsbError> {
+    if rgcb.len() < 8 {
+        return Err(XlsbError::Unrecognized {
+            typ: "PtgExtraArray",
+            val: format!("{} bytes", rgcb.len()),
+        });
+    }
+    let rows = read_u32(rgcb);
+    let cols = read_u32(&rgcb[4..]);
+    *rgcb = &rgcb[8..];
+
+    formula.push('{');
+    for row in 0..rows {
+        if row > 0 {
+            formula.push(';');
+        }
+        for col in 0..cols {
+            if col > 0 {
+                formula.push(',');
+            }
+            let typ = take_ser_item(rgcb, 1)?[0];
+            match typ {
+                0x00 => {
+                    // SerNum
+                    formula.push_str(&format!("{}", read_f64(take_ser_item(rgcb, 8)?)));
+                }
+                0x01 => {
+                    // SerStr
+                    let cch = read_u16(take_ser_item(rgcb, 2)?) as usize;
+                    formula.push('\"');
+                    formula.push_str(&UTF_16LE.decode(take_ser_item(rgcb, 2 * cch)?).0);
+                    formula.push('\"');
+                }
+                0x02 => {
+                    // SerBool
+                    let b = take_ser_item(rgcb, 1)?[0];
+                    formula.push_str(if b == 0 { "FALSE" } else { "TRUE" });
+                }
+                0x04 => {
+                    // SerErr
+                    match take_ser_item(rgcb, 4)?[0] {
+                        0x00 => formula.push_str("#NULL!"),
+                        0x07 => formula.push_str("#DIV/0!"),
+                        0x0F => formula.push_str("#VALUE!"),
+                        0x17 => formula.push_str("#REF!"),
+                        0x1D => formula.push_str("#NAME?"),
+                        0x24 => formula.push_str("#NUM!"),
+                        0x2A => formula.push_str("#N/A"),
+                        0x2B => formula.push_str("#GETTING_DATA"),
+                        0x2C => formula.push_str("#SPILL!"),
+                        0x2D => formula.push_str("#CALC!"),
+                        e => return Err(XlsbError::BErr(e)),
+                    }
+                }
+                t => {
+                    return Err(XlsbError::Unrecognized {
+                        typ: "SerAr",
+                        val: t.to_string(),
+                    })
+                }
+            }
+        }
+    }
+    formula.push('}');
+    Ok(())
+}
+
+/// Split the next `len` bytes of a SerAr item off `rgcb`
+fn take_ser_item<'a>(rgcb: &mut &'a [u8], len: usize) -> Result<&'a [u8], XlsbError> {
+    if rgcb.len() < len {
+        return Err(XlsbError::Unrecognized {
+            typ: "SerAr",
+            val: format!("expecting {len} bytes, got {}", rgcb.len()),
+        });
+    }
+    let (item, rest) = rgcb.split_at(len);
+    *rgcb = rest;
+    Ok(item)
+}
+
 fn cell_format<'a>(formats: &'a [CellFormat], buf: &[u8]) -> Option<&'a CellFormat> {
     // Parses a Cell (MS-XLSB 2.5.9) and determines if it references a Date format
 
     // iStyleRef is stored as a 24bit integer starting at the fifth byte
-    let style_ref = u32::from_le_bytes([buf[4], buf[5], buf[6], 0]);
+    // (truncated records have no format)
+    let style_ref = match buf.get(4..7)? {
+        &[b0, b1, b2] => u32::from_le_bytes([b0, b1, b2, 0]),
+        _ => return None,
+    };
 
     formats.get(style_ref as usize)
 }
@@ -1018,3 +1397,120 @@ fn check_for_password_protected<RS: Read + Seek>(reader: &mut RS) -> Result<(),
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_strings() {
+        let mut strings = SharedStrings::default();
+        for s in ["a", "", "été", "bcd"] {
+            strings.push(s);
+        }
+        assert_eq!(strings.len(), 4);
+        assert_eq!(strings.get(0).as_deref(), Some("a"));
+        assert_eq!(strings.get(1).as_deref(), Some(""));
+        assert_eq!(strings.get(2).as_deref(), Some("été"));
+        assert_eq!(strings.get(3).as_deref(), Some("bcd"));
+        assert_eq!(strings.get(4), None);
+    }
+
+    #[test]
+    fn test_shared_strings_lazy() {
+        let mut strings = SharedStrings {
+            lazy: true,
+            ..Default::default()
+        };
+        for s in ["a", "", "été"] {
+            let utf16: Vec<u8> = s.encode_utf16().flat_map(u16::to_le_bytes).collect();
+            strings.push_raw(&utf16);
+        }
+        assert_eq!(strings.len(), 3);
+        assert_eq!(strings.get(0).as_deref(), Some("a"));
+        assert_eq!(strings.get(1).as_deref(), Some(""));
+        assert_eq!(strings.get(2).as_deref(), Some("été"));
+        assert_eq!(strings.get(3), None);
+    }
+
+    #[test]
+    fn test_cell_format_short_buffer() {
+        let formats = [CellFormat::Other, CellFormat::DateTime];
+        assert_eq!(cell_format(&formats, &[0; 6]), None);
+        assert_eq!(cell_format(&formats, &[]), None);
+        assert_eq!(
+            cell_format(&formats, &[0, 0, 0, 0, 1, 0, 0]),
+            Some(&CellFormat::DateTime)
+        );
+    }
+
+    #[test]
+    fn test_parse_formula_array() {
+        // ={1,2;"a",TRUE}
+        let rgce = [0x40, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut rgcb = vec![2, 0, 0, 0, 2, 0, 0, 0];
+        rgcb.push(0x00);
+        rgcb.extend_from_slice(&1f64.to_le_bytes());
+        rgcb.push(0x00);
+        rgcb.extend_from_slice(&2f64.to_le_bytes());
+        rgcb.extend_from_slice(&[0x01, 1, 0, b'a', 0]);
+        rgcb.extend_from_slice(&[0x02, 1]);
+        assert_eq!(
+            parse_formula(&rgce, &rgcb, &[], &[]).unwrap(),
+            "{1,2;\"a\",TRUE}"
+        );
+    }
+
+    #[test]
+    fn test_parse_formula_array_truncated() {
+        let rgce = [0x40, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let header = [1, 0, 0, 0, 1, 0, 0, 0];
+        for item in [
+            &[][..],
+            &[0x00, 0, 0, 0],    // SerNum
+            &[0x01, 2, 0, b'a'], // SerStr
+            &[0x01, 2],
+            &[0x02],       // SerBool
+            &[0x04, 0x07], // SerErr
+        ] {
+            let rgcb = [&header[..], item].concat();
+            assert!(matches!(
+                parse_formula(&rgce, &rgcb, &[], &[]),
+                Err(XlsbError::Unrecognized { typ: "SerAr", .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_formula_mem_area() {
+        // =SUM(A1:B2) wrapped in a PtgMemArea
+        let mut rgce = vec![0x26, 0, 0, 0, 0, 13, 0];
+        rgce.extend_from_slice(&[0x25, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0]);
+        rgce.extend_from_slice(&[0x19, 0x10, 0, 0]);
+        let mut rgcb = vec![1, 0, 0, 0];
+        rgcb.extend_from_slice(&[0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(
+            parse_formula(&rgce, &rgcb, &[], &[]).unwrap(),
+            "SUM($A$1:$B$2)"
+        );
+
+        // truncated PtgExtraMem
+        let mut rgce = vec![0x26, 0, 0, 0, 0, 13, 0];
+        rgce.extend_from_slice(&[0x25, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0]);
+        rgce.extend_from_slice(&[0x19, 0x10, 0, 0]);
+        for rgcb in [&[][..], &[2, 0, 0, 0, 0, 0, 0, 0]] {
+            assert!(matches!(
+                parse_formula(&rgce, rgcb, &[], &[]),
+                Err(XlsbError::Unrecognized {
+                    typ: "PtgExtraMem",
+                    ..
+                })
+            ));
+        }
+
+        // PtgMemNoMem wrapping a reference
+        let mut rgce = vec![0x28, 0, 0, 0, 0, 7, 0];
+        rgce.extend_from_slice(&[0x24, 2, 0, 0, 0, 3, 0xC0]);
+        assert_eq!(parse_formula(&rgce, &[], &[], &[]).unwrap(), "D3");
+    }
+}
@@ -1,5 +1,6 @@
+use log::warn;
 use quick_xml::{
-    events::{attributes::Attribute, BytesStart, Event},
+    events::{BytesStart, Event},
     name::QName,
 };
 use std::{borrow::Borrow, collections::HashMap};
@@ -11,7 +12,7 @@ use super::{
 use crate::{
     datatype::DataRef,
     formats::{format_excel_f64_ref, CellFormat},
-    Cell, XlsxError,
+    Cell, CellErrorType, XlsxError,
 };
 
 type FormulaMap = HashMap<(u32, u32), (i64, i64)>;
@@ -25,6 +26,8 @@ pub struct XlsxCellReader<'a> {
     dimensions: Dimensions,
     row_index: u32,
     col_index: u32,
+    style_index: usize,
+    is_formula: bool,
     buf: Vec<u8>,
     cell_buf: Vec<u8>,
     formulas: Vec<Option<(String, FormulaMap)>>,
@@ -40,22 +43,18 @@ impl<'a> XlsxCellReader<'a> {
         let mut buf = Vec::with_capacity(1024);
         let mut dimensions = Dimensions::default();
         let mut sh_type = None;
-        'xml: loop {
+        loop {
             buf.clear();
             match xml.read_event_into(&mut buf).map_err(XlsxError::Xml)? {
                 Event::Start(ref e) => match e.local_name().as_ref() {
                     b"dimension" => {
-                        for a in e.attributes() {
-                            if let Attribute {
-                                key: QName(b"ref"),
-                                value: rdim,
-                            } = a.map_err(XlsxError::XmlAttr)?
-                            {
-                                dimensions = get_dimension(&rdim)?;
-                                continue 'xml;
-                            }
+                        // the declared dimension is only a hint, the range is computed
+                        // from the cells actually read
+                        match get_attribute(e.attributes(), QName(b"ref"))?.map(get_dimension) {
+                            Some(Ok(dim)) => dimensions = dim,
+                            Some(Err(err)) => warn!("ignoring invalid dimension: {err}"),
+                            None => warn!("ignoring dimension without 'ref' attribute"),
                         }
-                        return Err(XlsxError::UnexpectedNode("dimension"));
                     }
                     b"sheetData" => break,
                     typ => {
@@ -82,6 +81,8 @@ impl<'a> XlsxCellReader<'a> {
             dimensions,
             row_index: 0,
             col_index: 0,
+            style_index: 0,
+            is_formula: false,
             buf: Vec::with_capacity(1024),
             cell_buf: Vec::with_capacity(1024),
             formulas: Vec::with_capacity(1024),
@@ -92,6 +93,24 @@ impl<'a> XlsxCellReader<'a> {
         self.dimensions
     }
 
+    /// Style index (the `s` attribute) of the last cell read by `next_cell`
+    pub fn style_index(&self) -> usize {
+        self.style_index
+    }
+
+    /// Is the last cell read by `next_cell` a formula result
+    ///
+    /// This tells apart the string results of formulas (`t="str"` cells) from
+    /// literal text, which are both read as strings.
+    ///
+    /// This is only available on the reader returned by
+    /// [`Xlsx::worksheet_cells_reader`](crate::Xlsx::worksheet_cells_reader): ranges and
+    /// [`Xlsx::worksheet_cells_formatted`](crate::Xlsx::worksheet_cells_formatted) items
+    /// do not carry it.
+    pub fn is_formula(&self) -> bool {
+        self.is_formula
+    }
+
     pub fn next_cell(&mut self) -> Result<Option<Cell<DataRef<'a>>>, XlsxError> {
         loop {
             self.buf.clear();
@@ -118,11 +137,17 @@ impl<'a> XlsxCellReader<'a> {
                     } else {
                         (self.row_index, self.col_index)
                     };
+                    self.style_index = get_attribute(c_element.attributes(), QName(b"s"))?
+                        .and_then(|s| std::str::from_utf8(s).ok()?.parse().ok())
+                        .unwrap_or(0);
+                    self.is_formula =
+                        get_attribute(c_element.attributes(), QName(b"t"))? == Some(b"str");
                     let mut value = DataRef::Empty;
                     loop {
                         self.cell_buf.clear();
                         match self.xml.read_event_into(&mut self.cell_buf) {
                             Ok(Event::Start(ref e)) => {
+                                self.is_formula |= e.local_name().as_ref() == b"f";
                                 value = read_value(
                                     self.strings,
                                     self.formats,
@@ -151,6 +176,141 @@ impl<'a> XlsxCellReader<'a> {
         }
     }
 
+    /// Get the next cell with its original text, before any value conversion
+    ///
+    /// This is the `<v>` content, the inline string or, for shared strings, the referenced string.
+    pub fn next_raw_text(&mut self) -> Result<Option<Cell<String>>, XlsxError> {
+        loop {
+            self.buf.clear();
+            match self.xml.read_event_into(&mut self.buf) {
+                Ok(Event::Start(ref row_element))
+                    if row_element.local_name().as_ref() == b"row" =>
+                {
+                    let attribute = get_attribute(row_element.attributes(), QName(b"r"))?;
+                    if let Some(range) = attribute {
+                        let row = get_row(range)?;
+                        self.row_index = row;
+                    }
+                }
+                Ok(Event::End(ref row_element)) if row_element.local_name().as_ref() == b"row" => {
+                    self.row_index += 1;
+                    self.col_index = 0;
+                }
+                Ok(Event::Start(ref c_element)) if c_element.local_name().as_ref() == b"c" => {
+                    let attribute = get_attribute(c_element.attributes(), QName(b"r"))?;
+                    let pos = if let Some(range) = attribute {
+                        let (row, col) = get_row_column(range)?;
+                        self.col_index = col;
+                        (row, col)
+                    } else {
+                        (self.row_index, self.col_index)
+                    };
+                    let is_shared_string =
+                        get_attribute(c_element.attributes(), QName(b"t"))? == Some(b"s");
+                    let mut value = String::new();
+                    loop {
+                        self.cell_buf.clear();
+                        match self.xml.read_event_into(&mut self.cell_buf) {
+                            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                                b"is" => {
+                                    if let Some(s) = read_string(&mut self.xml, e.name())? {
+                                        value = s;
+                                    }
+                                }
+                                b"v" => {
+                                    let mut v_buf = Vec::new();
+                                    loop {
+                                        v_buf.clear();
+                                        match self.xml.read_event_into(&mut v_buf)? {
+                                            Event::Text(t) => value.push_str(&t.unescape()?),
+                                            Event::End(end) if end.name() == e.name() => break,
+                                            Event::Eof => return Err(XlsxError::XmlEof("v")),
+                                            _ => (),
+                                        }
+                                    }
+                                    if is_shared_string {
+                                        if let Some(s) = value
+                                            .parse::<usize>()
+                                            .ok()
+                                            .and_then(|idx| self.strings.get(idx))
+                                        {
+                                            value = s.clone();
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    self.xml.read_to_end_into(e.name(), &mut Vec::new())?;
+                                }
+                            },
+                            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c" => break,
+                            Ok(Event::Eof) => return Err(XlsxError::XmlEof("c")),
+                            Err(e) => return Err(XlsxError::Xml(e)),
+                            _ => (),
+                        }
+                    }
+                    self.col_index += 1;
+                    return Ok(Some(Cell::new(pos, value)));
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sheetData" => {
+                    return Ok(None);
+                }
+                Ok(Event::Eof) => return Err(XlsxError::XmlEof("sheetData")),
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+    }
+
+    /// Get the next cell having a value metadata index (`vm` attribute, 1-based)
+    pub fn next_value_metadata(&mut self) -> Result<Option<Cell<usize>>, XlsxError> {
+        loop {
+            self.buf.clear();
+            match self.xml.read_event_into(&mut self.buf) {
+                Ok(Event::Start(ref row_element))
+                    if row_element.local_name().as_ref() == b"row" =>
+                {
+                    let attribute = get_attribute(row_element.attributes(), QName(b"r"))?;
+                    if let Some(range) = attribute {
+                        let row = get_row(range)?;
+                        self.row_index = row;
+                    }
+                }
+                Ok(Event::End(ref row_element)) if row_element.local_name().as_ref() == b"row" => {
+                    self.row_index += 1;
+                    self.col_index = 0;
+                }
+                Ok(Event::Start(ref c_element)) if c_element.local_name().as_ref() == b"c" => {
+                    let attribute = get_attribute(c_element.attributes(), QName(b"r"))?;
+                    let pos = if let Some(range) = attribute {
+                        let (row, col) = get_row_column(range)?;
+                        self.col_index = col;
+                        (row, col)
+                    } else {
+                        (self.row_index, self.col_index)
+                    };
+                    let vm = match get_attribute(c_element.attributes(), QName(b"vm"))? {
+                        Some(v) => Some(self.xml.decoder().decode(v)?.parse::<usize>()?),
+                        None => None,
+                    };
+                    self.cell_buf.clear();
+                    self.xml
+                        .read_to_end_into(c_element.name(), &mut self.cell_buf)
+                        .map_err(XlsxError::Xml)?;
+                    self.col_index += 1;
+                    if let Some(vm) = vm {
+                        return Ok(Some(Cell::new(pos, vm)));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sheetData" => {
+                    return Ok(None);
+                }
+                Ok(Event::Eof) => return Err(XlsxError::XmlEof("sheetData")),
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+    }
+
     pub fn next_formula(&mut self) -> Result<Option<Cell<String>>, XlsxError> {
         loop {
             self.buf.clear();
@@ -344,7 +504,16 @@ fn read_v<'s>(
         Some(b"s") => {
             // shared string
             let idx: usize = v.parse()?;
-            Ok(DataRef::SharedString(&strings[idx]))
+            match strings.get(idx) {
+                Some(s) => Ok(DataRef::SharedString(s)),
+                None => {
+                    log::warn!(
+                        "shared string index {idx} out of range ({} strings)",
+                        strings.len()
+                    );
+                    Ok(DataRef::Error(CellErrorType::GettingData))
+                }
+            }
         }
         Some(b"b") => {
             // boolean
@@ -366,12 +535,16 @@ fn read_v<'s>(
             // n - number
             if v.is_empty() {
                 Ok(DataRef::Empty)
+            } else if cell_format == Some(&CellFormat::Text) {
+                // text formatted cells keep their text, e.g. leading zeros
+                Ok(DataRef::String(v))
             } else {
                 v.parse()
                     .map(|n| format_excel_f64_ref(n, cell_format, is_1904))
                     .map_err(XlsxError::ParseFloat)
             }
         }
+        None if cell_format == Some(&CellFormat::Text) => Ok(DataRef::String(v)),
         None => {
             // If type is not known, we try to parse as Float for utility, but fall back to
             // String if this fails.
@@ -1,29 +1,34 @@
 mod cells_reader;
 
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::io::BufReader;
 use std::io::{Read, Seek};
 use std::str::FromStr;
 
 use log::warn;
+use quick_xml::encoding::Decoder;
 use quick_xml::events::attributes::{Attribute, Attributes};
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::name::QName;
 use quick_xml::Reader as XmlReader;
 use zip::read::{ZipArchive, ZipFile};
 use zip::result::ZipError;
 
 use crate::datatype::DataRef;
-use crate::formats::{builtin_format_by_id, detect_custom_number_format, CellFormat};
+use crate::formats::{
+    builtin_format_by_id, builtin_format_code_by_id, detect_custom_number_format, CellFormat,
+};
+use crate::utils::{io_error_eq, xml_error_eq, zip_error_eq};
 use crate::vba::VbaProject;
 use crate::{
-    Cell, CellErrorType, Data, Dimensions, HeaderRow, Metadata, Range, Reader, ReaderRef, Sheet,
-    SheetType, SheetVisible, Table,
+    Cell, CellErrorType, CellType, Data, Dimensions, HeaderRow, Metadata, Range, Reader, ReaderRef,
+    Sheet, SheetType, SheetVisible, Table,
 };
 pub use cells_reader::XlsxCellReader;
 
-pub(crate) type XlReader<'a> = XmlReader<BufReader<ZipFile<'a>>>;
+/// A `quick_xml` reader over a part of a xlsx archive
+pub type XlReader<'a> = XmlReader<BufReader<ZipFile<'a>>>;
 
 /// Maximum number of rows allowed in an xlsx file
 pub const MAX_ROWS: u32 = 1_048_576;
@@ -89,6 +94,17 @@ pub enum XlsxError {
     TableNotFound(String),
     /// The specified sheet is not a worksheet
     NotAWorksheet(String),
+    /// The specified sheet is not a chartsheet
+    NotAChartsheet(String),
+    /// The file is an incomplete zip archive (e.g. an interrupted download)
+    Truncated,
+    /// A cell read with `worksheet_range_f64_strict` is not a number
+    NotANumber {
+        /// Absolute row of the cell
+        row: u32,
+        /// Absolute column of the cell
+        col: u32,
+    },
 }
 
 from_err!(std::io::Error, XlsxError, Io);
@@ -139,6 +155,11 @@ impl std::fmt::Display for XlsxError {
             XlsxError::Password => write!(f, "Workbook is password protected"),
             XlsxError::TableNotFound(n) => write!(f, "Table '{n}' not found"),
             XlsxError::NotAWorksheet(typ) => write!(f, "Expecting a worksheet, got {typ}"),
+            XlsxError::NotAChartsheet(n) => write!(f, "Sheet '{n}' is not a chartsheet"),
+            XlsxError::Truncated => write!(f, "File is truncated, the zip archive is incomplete"),
+            XlsxError::NotANumber { row, col } => {
+                write!(f, "Cell at row {row}, column {col} is not a number")
+            }
         }
     }
 }
@@ -158,6 +179,47 @@ impl std::error::Error for XlsxError {
     }
 }
 
+impl PartialEq for XlsxError {
+    fn eq(&self, other: &Self) -> bool {
+        use XlsxError::*;
+        match (self, other) {
+            (Io(a), Io(b)) => io_error_eq(a, b),
+            (Zip(a), Zip(b)) => zip_error_eq(a, b),
+            (Vba(a), Vba(b)) => a == b,
+            (Xml(a), Xml(b)) => xml_error_eq(a, b),
+            (XmlAttr(a), XmlAttr(b)) => a == b,
+            (Parse(a), Parse(b)) => a == b,
+            (ParseFloat(a), ParseFloat(b)) => a == b,
+            (ParseInt(a), ParseInt(b)) => a == b,
+            (XmlEof(a), XmlEof(b)) => a == b,
+            (UnexpectedNode(a), UnexpectedNode(b)) => a == b,
+            (FileNotFound(a), FileNotFound(b)) => a == b,
+            (RelationshipNotFound, RelationshipNotFound) => true,
+            (Alphanumeric(a), Alphanumeric(b)) => a == b,
+            (NumericColumn(a), NumericColumn(b)) => a == b,
+            (DimensionCount(a), DimensionCount(b)) => a == b,
+            (CellTAttribute(a), CellTAttribute(b)) => a == b,
+            (RangeWithoutColumnComponent, RangeWithoutColumnComponent) => true,
+            (RangeWithoutRowComponent, RangeWithoutRowComponent) => true,
+            (Unexpected(a), Unexpected(b)) => a == b,
+            (Unrecognized { typ: t1, val: v1 }, Unrecognized { typ: t2, val: v2 }) => {
+                t1 == t2 && v1 == v2
+            }
+            (CellError(a), CellError(b)) => a == b,
+            (Password, Password) => true,
+            (WorksheetNotFound(a), WorksheetNotFound(b)) => a == b,
+            (TableNotFound(a), TableNotFound(b)) => a == b,
+            (NotAWorksheet(a), NotAWorksheet(b)) => a == b,
+            (NotAChartsheet(a), NotAChartsheet(b)) => a == b,
+            (Truncated, Truncated) => true,
+            (NotANumber { row: r1, col: c1 }, NotANumber { row: r2, col: c2 }) => {
+                r1 == r2 && c1 == c2
+            }
+            _ => false,
+        }
+    }
+}
+
 impl FromStr for CellErrorType {
     type Err = XlsxError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -169,6 +231,8 @@ impl FromStr for CellErrorType {
             "#NUM!" => Ok(CellErrorType::Num),
             "#REF!" => Ok(CellErrorType::Ref),
             "#VALUE!" => Ok(CellErrorType::Value),
+            "#SPILL!" => Ok(CellErrorType::Spill),
+            "#CALC!" => Ok(CellErrorType::Calc),
             _ => Err(XlsxError::CellError(s.into())),
         }
     }
@@ -186,8 +250,12 @@ pub struct Xlsx<RS> {
     sheets: Vec<(String, String)>,
     /// Tables: Name, Sheet, Columns, Data dimensions
     tables: Tables,
+    /// Workbook protection
+    workbook_protection: Option<WorkbookProtection>,
     /// Cell (number) formats
     formats: Vec<CellFormat>,
+    /// Number format codes, indexed like `formats`
+    format_codes: Vec<Option<String>>,
     /// 1904 datetime system
     is_1904: bool,
     /// Metadata
@@ -199,16 +267,235 @@ pub struct Xlsx<RS> {
     merged_regions: Option<Vec<(String, String, Dimensions)>>,
     /// Reader options
     options: XlsxOptions,
+    /// Content types, by extension and by part name
+    content_types: ContentTypes,
+    /// Theme major and minor latin fonts
+    theme_fonts: Option<(String, String)>,
+    /// Parsed sheet structures, by sheet name
+    sheet_structures: BTreeMap<String, SheetStructure>,
+    /// Are the shared strings and styles read
+    cell_parts_loaded: bool,
+}
+
+/// Structure of a worksheet read in a single pass, cached by sheet name
+#[derive(Debug, Clone)]
+struct SheetStructure {
+    /// Declared dimensions
+    dimensions: Dimensions,
+    /// Merged cells
+    merge_cells: Vec<Dimensions>,
 }
 
-/// Xlsx reader options
+/// Content types declared in `[Content_Types].xml`, keys are lowercase
 #[derive(Debug, Default)]
+struct ContentTypes {
+    defaults: BTreeMap<String, String>,
+    overrides: BTreeMap<String, String>,
+}
+
+/// Xlsx reader options, see [`Xlsx::new_with_options`]
+///
+/// Reading options can also be changed after opening with the `Xlsx::with_*` methods.
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-struct XlsxOptions {
+pub struct XlsxOptions {
+    /// Row to use as header
     pub header_row: HeaderRow,
+    /// Maximum number of rows read after the header row, see [`Xlsx::with_max_rows`]
+    pub max_rows: Option<u32>,
+    /// Pad ranges to the declared dimensions, see [`Xlsx::with_declared_dimensions`]
+    pub declared_dimensions: bool,
+    /// Stop at the first empty row after the data, see [`Xlsx::with_stop_at_empty_row`]
+    pub stop_at_empty_row: bool,
+    /// Trim string cells, see [`Xlsx::with_trim_strings`]
+    pub trim_strings: bool,
+    /// Do not read the shared strings and styles when opening the workbook
+    ///
+    /// They are read on the first worksheet cells read instead.
+    pub lazy_cell_parts: bool,
+    /// Do not read the pictures when opening the workbook, see [`Xlsx::load_pictures`]
+    #[cfg(feature = "picture")]
+    pub lazy_pictures: bool,
 }
 
 impl<RS: Read + Seek> Xlsx<RS> {
+    /// Creates a new instance using `XlsxOptions`
+    ///
+    /// By default, opening a workbook reads the workbook, shared strings, styles, theme
+    /// and, with the `picture` feature, pictures. Listing sheets or reading workbook
+    /// level metadata only needs the workbook, so the loading of the other parts can be
+    /// deferred, which is much faster for large files:
+    /// - shared strings and styles (`lazy_cell_parts`) are read by the first worksheet
+    ///   cells read or by [`load_cell_parts`](Self::load_cell_parts). Until then
+    ///   [`shared_string`](Self::shared_string),
+    ///   [`shared_string_count`](Self::shared_string_count) and
+    ///   [`cell_format_string`](Self::cell_format_string) see no strings nor styles.
+    /// - pictures (`lazy_pictures`) are read by [`load_pictures`](Self::load_pictures).
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{Reader, Xlsx, XlsxOptions};
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut options = XlsxOptions::default();
+    /// options.lazy_cell_parts = true;
+    /// let reader = BufReader::new(File::open(path).unwrap());
+    /// let mut workbook = Xlsx::new_with_options(reader, options).unwrap();
+    /// assert_eq!(workbook.shared_string_count(), 0);
+    /// assert!(workbook.sheet_names().contains(&"issue2".to_string()));
+    /// // shared strings are read here
+    /// assert!(workbook.worksheet_range("issue2").is_ok());
+    /// assert!(workbook.shared_string_count() > 0);
+    /// ```
+    pub fn new_with_options(mut reader: RS, options: XlsxOptions) -> Result<Self, XlsxError> {
+        check_for_password_protected(&mut reader)?;
+        check_for_truncated(&mut reader)?;
+
+        let mut xlsx = Xlsx {
+            zip: ZipArchive::new(reader)?,
+            strings: Vec::new(),
+            workbook_protection: None,
+            formats: Vec::new(),
+            format_codes: Vec::new(),
+            is_1904: false,
+            sheets: Vec::new(),
+            tables: None,
+            metadata: Metadata::default(),
+            #[cfg(feature = "picture")]
+            pictures: None,
+            merged_regions: None,
+            options,
+            content_types: ContentTypes::default(),
+            theme_fonts: None,
+            sheet_structures: BTreeMap::new(),
+            cell_parts_loaded: false,
+        };
+        // content types are only informative, the parts are found by their relationships
+        if let Err(e) = xlsx.read_content_types() {
+            warn!("ignoring invalid [Content_Types].xml: {e}");
+            xlsx.content_types = ContentTypes::default();
+        }
+        if !xlsx.options.lazy_cell_parts {
+            xlsx.load_cell_parts()?;
+        }
+        // the theme is not needed to read the cells
+        if let Err(e) = xlsx.read_theme() {
+            warn!("ignoring invalid theme: {e}");
+        }
+        let relationships = xlsx.read_relationships()?;
+        xlsx.read_workbook(&relationships)?;
+        #[cfg(feature = "picture")]
+        if !xlsx.options.lazy_pictures {
+            xlsx.read_pictures()?;
+        }
+
+        Ok(xlsx)
+    }
+
+    /// Read the pictures, when deferred with `XlsxOptions::lazy_pictures`
+    ///
+    /// Does nothing if they are already read.
+    #[cfg(feature = "picture")]
+    pub fn load_pictures(&mut self) -> Result<(), XlsxError> {
+        if self.pictures.is_none() {
+            self.read_pictures()?;
+        }
+        Ok(())
+    }
+
+    /// Read the shared strings and styles, when deferred with `XlsxOptions::lazy_cell_parts`
+    ///
+    /// Reading worksheet cells loads them as needed, this is only required before
+    /// [`shared_string`](Self::shared_string), [`shared_string_count`](Self::shared_string_count)
+    /// or [`cell_format_string`](Self::cell_format_string). Does nothing if they are already read.
+    pub fn load_cell_parts(&mut self) -> Result<(), XlsxError> {
+        if !self.cell_parts_loaded {
+            // start over after a failed load
+            self.strings.clear();
+            self.formats.clear();
+            self.format_codes.clear();
+            self.read_shared_strings()?;
+            self.read_styles()?;
+            self.cell_parts_loaded = true;
+        }
+        Ok(())
+    }
+
+    fn read_content_types(&mut self) -> Result<(), XlsxError> {
+        let mut xml = match xml_reader(&mut self.zip, "[Content_Types].xml") {
+            None => return Ok(()),
+            Some(x) => x?,
+        };
+        let mut buf = Vec::with_capacity(256);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    let (key, map) = match e.local_name().as_ref() {
+                        b"Default" => (QName(b"Extension"), &mut self.content_types.defaults),
+                        b"Override" => (QName(b"PartName"), &mut self.content_types.overrides),
+                        _ => continue,
+                    };
+                    let name = get_attribute(e.attributes(), key)?;
+                    let typ = get_attribute(e.attributes(), QName(b"ContentType"))?;
+                    if let (Some(name), Some(typ)) = (name, typ) {
+                        let name = xml.decoder().decode(name)?;
+                        let name = name.trim_start_matches('/').to_ascii_lowercase();
+                        map.insert(name, xml.decoder().decode(typ)?.into_owned());
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"Types" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_theme(&mut self) -> Result<(), XlsxError> {
+        let mut xml = match xml_reader(&mut self.zip, "xl/theme/theme1.xml") {
+            None => return Ok(()),
+            Some(x) => x?,
+        };
+        let mut buf = Vec::with_capacity(1024);
+        let (mut major, mut minor) = (None, None);
+        let mut in_major = None;
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                    b"majorFont" => in_major = Some(true),
+                    b"minorFont" => in_major = Some(false),
+                    b"latin" => {
+                        let font = match in_major {
+                            Some(true) => &mut major,
+                            Some(false) => &mut minor,
+                            None => continue,
+                        };
+                        if let Some(typeface) = get_attribute(e.attributes(), QName(b"typeface"))? {
+                            *font = Some(xml.decoder().decode(typeface)?.into_owned());
+                        }
+                    }
+                    _ => (),
+                },
+                Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                    b"majorFont" | b"minorFont" => in_major = None,
+                    b"fontScheme" => break,
+                    _ => (),
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        if let (Some(major), Some(minor)) = (major, minor) {
+            self.theme_fonts = Some((major, minor));
+        }
+        Ok(())
+    }
+
     fn read_shared_strings(&mut self) -> Result<(), XlsxError> {
         let mut xml = match xml_reader(&mut self.zip, "xl/sharedStrings.xml") {
             None => return Ok(()),
@@ -257,10 +544,10 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                         key: QName(b"numFmtId"),
                                         value: v,
                                     } => id.extend_from_slice(&v),
-                                    Attribute {
-                                        key: QName(b"formatCode"),
-                                        value: v,
-                                    } => format = xml.decoder().decode(&v)?.into_owned(),
+                                    a if a.key == QName(b"formatCode") => {
+                                        format =
+                                            a.decode_and_unescape_value(xml.decoder())?.into_owned()
+                                    }
                                     _ => (),
                                 }
                             }
@@ -278,17 +565,24 @@ impl<RS: Read + Seek> Xlsx<RS> {
                     inner_buf.clear();
                     match xml.read_event_into(&mut inner_buf) {
                         Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"xf" => {
-                            self.formats.push(
-                                e.attributes()
-                                    .filter_map(|a| a.ok())
-                                    .find(|a| a.key == QName(b"numFmtId"))
-                                    .map_or(CellFormat::Other, |a| {
-                                        match number_formats.get(&*a.value) {
-                                            Some(fmt) => detect_custom_number_format(fmt),
-                                            None => builtin_format_by_id(&a.value),
-                                        }
-                                    }),
-                            );
+                            let id = e
+                                .attributes()
+                                .filter_map(|a| a.ok())
+                                .find(|a| a.key == QName(b"numFmtId"));
+                            let (format, code) = match id {
+                                Some(a) => match number_formats.get(&*a.value) {
+                                    Some(fmt) => {
+                                        (detect_custom_number_format(fmt), Some(fmt.clone()))
+                                    }
+                                    None => (
+                                        builtin_format_by_id(&a.value),
+                                        builtin_format_code_by_id(&a.value).map(String::from),
+                                    ),
+                                },
+                                None => (CellFormat::Other, None),
+                            };
+                            self.formats.push(format);
+                            self.format_codes.push(code);
                         }
                         Ok(Event::End(ref e)) if e.local_name().as_ref() == b"cellXfs" => break,
                         Ok(Event::Eof) => return Err(XlsxError::XmlEof("cellXfs")),
@@ -349,34 +643,24 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                     }
                                 }
                             }
-                            Attribute {
-                                key: QName(b"r:id"),
-                                value: v,
-                            }
-                            | Attribute {
-                                key: QName(b"relationships:id"),
-                                value: v,
-                            } => {
-                                let r = &relationships
+                            // the relationships namespace differs between transitional and
+                            // strict files, only its prefixed `id` attribute matters
+                            Attribute { key, value: v }
+                                if key.prefix().is_some() && key.local_name().as_ref() == b"id" =>
+                            {
+                                let r = relationships
                                     .get(&*v)
-                                    .ok_or(XlsxError::RelationshipNotFound)?[..];
-                                // target may have pre-prended "/xl/" or "xl/" path;
-                                // strip if present
-                                path = if r.starts_with("/xl/") {
-                                    r[1..].to_string()
-                                } else if r.starts_with("xl/") {
-                                    r.to_string()
-                                } else {
-                                    format!("xl/{}", r)
-                                };
+                                    .ok_or(XlsxError::RelationshipNotFound)?;
+                                path = resolve_target("xl", r);
                             }
                             _ => (),
                         }
                     }
-                    let typ = match path.split('/').nth(1) {
+                    let typ = match path.rsplit('/').nth(1) {
                         Some("worksheets") => SheetType::WorkSheet,
                         Some("chartsheets") => SheetType::ChartSheet,
                         Some("dialogsheets") => SheetType::DialogSheet,
+                        Some("macrosheets") => SheetType::MacroSheet,
                         _ => {
                             return Err(XlsxError::Unrecognized {
                                 typ: "sheet:type",
@@ -391,7 +675,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                     });
                     self.sheets.push((name, path));
                 }
-                Ok(Event::Start(ref e)) if e.name().as_ref() == b"workbookPr" => {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"workbookPr" => {
                     self.is_1904 = match e.try_get_attribute("date1904")? {
                         Some(c) => ["1", "true"].contains(
                             &c.decode_and_unescape_value(xml.decoder())
@@ -401,6 +685,22 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         None => false,
                     };
                 }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"workbookProtection" => {
+                    let mut protection = WorkbookProtection::default();
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        let flag = &*a.value == b"1" || &*a.value == b"true";
+                        match a.key {
+                            QName(b"lockStructure") => protection.lock_structure = flag,
+                            QName(b"lockWindows") => protection.lock_windows = flag,
+                            QName(b"workbookPassword") | QName(b"workbookHashValue") => {
+                                protection.has_password = !a.value.is_empty()
+                            }
+                            _ => (),
+                        }
+                    }
+                    self.workbook_protection = Some(protection);
+                }
                 Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"definedName" => {
                     if let Some(a) = e
                         .attributes()
@@ -431,6 +731,43 @@ impl<RS: Read + Seek> Xlsx<RS> {
         Ok(())
     }
 
+    /// Read the targets of the relationships of a part having the given type
+    /// (e.g. `drawing`), resolved into paths within the archive
+    fn read_part_relationships(
+        &mut self,
+        part: &str,
+        rel_type: &str,
+    ) -> Result<Vec<String>, XlsxError> {
+        let (base_folder, file_name) = part.rsplit_once('/').unwrap_or(("", part));
+        let rel_path = resolve_target(base_folder, &format!("_rels/{}.rels", file_name));
+        let mut xml = match xml_reader(&mut self.zip, &rel_path) {
+            None => return Ok(Vec::new()),
+            Some(x) => x?,
+        };
+        let mut targets = Vec::new();
+        let mut buf = Vec::with_capacity(64);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"Relationship" => {
+                    let typ = get_attribute(e.attributes(), QName(b"Type"))?.unwrap_or_default();
+                    if typ.rsplit(|c| *c == b'/').next() != Some(rel_type.as_bytes()) {
+                        continue;
+                    }
+                    if let Some(target) = get_attribute(e.attributes(), QName(b"Target"))? {
+                        let target = xml.decoder().decode(target)?;
+                        targets.push(resolve_target(base_folder, &target));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"Relationships" => break,
+                Ok(Event::Eof) => return Err(XlsxError::XmlEof("Relationships")),
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(targets)
+    }
+
     fn read_relationships(&mut self) -> Result<BTreeMap<Vec<u8>, String>, XlsxError> {
         let mut xml = match xml_reader(&mut self.zip, "xl/_rels/workbook.xml.rels") {
             None => {
@@ -476,9 +813,8 @@ impl<RS: Read + Seek> Xlsx<RS> {
     fn read_table_metadata(&mut self) -> Result<(), XlsxError> {
         let mut new_tables = Vec::new();
         for (sheet_name, sheet_path) in &self.sheets {
-            let last_folder_index = sheet_path.rfind('/').expect("should be in a folder");
-            let (base_folder, file_name) = sheet_path.split_at(last_folder_index);
-            let rel_path = format!("{}/_rels{}.rels", base_folder, file_name);
+            let (base_folder, file_name) = sheet_path.rsplit_once('/').unwrap_or(("", sheet_path));
+            let rel_path = resolve_target(base_folder, &format!("_rels/{}.rels", file_name));
 
             let mut table_locations = Vec::new();
             let mut buf = Vec::with_capacity(64);
@@ -508,22 +844,15 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                     Attribute {
                                         key: QName(b"Type"),
                                         value: v,
-                                    } => table_type = *v == b"http://schemas.openxmlformats.org/officeDocument/2006/relationships/table"[..],
+                                    } => {
+                                        table_type =
+                                            v.rsplit(|c| *c == b'/').next() == Some(b"table")
+                                    }
                                     _ => (),
                                 }
                             }
-                            if table_type {
-                                if target.starts_with("../") {
-                                    // this is an incomplete implementation, but should be good enough for excel
-                                    let new_index =
-                                        base_folder.rfind('/').expect("Must be a parent folder");
-                                    let full_path =
-                                        format!("{}{}", &base_folder[..new_index], &target[2..]);
-                                    table_locations.push(full_path);
-                                } else if target.is_empty() { // do nothing
-                                } else {
-                                    table_locations.push(target);
-                                }
+                            if table_type && !target.is_empty() {
+                                table_locations.push(resolve_target(base_folder, &target));
                             }
                         }
                         Ok(Event::End(ref e)) if e.local_name().as_ref() == b"Relationships" => {
@@ -655,32 +984,14 @@ impl<RS: Read + Seek> Xlsx<RS> {
     // sheets must be added before this is called!!
     fn read_merged_regions(&mut self) -> Result<(), XlsxError> {
         let mut regions = Vec::new();
-        for (sheet_name, sheet_path) in &self.sheets {
-            // we need another mutable borrow of self.zip later so we enclose this borrow within braces
-            {
-                let mut xml = match xml_reader(&mut self.zip, sheet_path) {
-                    None => continue,
-                    Some(x) => x?,
-                };
-                let mut buf = Vec::new();
-                loop {
-                    buf.clear();
-                    match xml.read_event_into(&mut buf) {
-                        Ok(Event::Start(ref e)) if e.local_name() == QName(b"mergeCell").into() => {
-                            if let Some(attr) = get_attribute(e.attributes(), QName(b"ref"))? {
-                                let dismension = get_dimension(attr)?;
-                                regions.push((
-                                    sheet_name.to_string(),
-                                    sheet_path.to_string(),
-                                    dismension,
-                                ));
-                            }
-                        }
-                        Ok(Event::Eof) => break,
-                        Err(e) => return Err(XlsxError::Xml(e)),
-                        _ => (),
-                    }
-                }
+        for (sheet_name, sheet_path) in self.sheets.clone() {
+            // reuse the cached sheet structures rather than parsing the sheets again
+            let merge_cells = match self.worksheet_merge_cells(&sheet_name) {
+                None => continue,
+                Some(cells) => cells?,
+            };
+            for dimension in merge_cells {
+                regions.push((sheet_name.clone(), sheet_path.clone(), dimension));
             }
         }
         self.merged_regions = Some(regions);
@@ -810,37 +1121,148 @@ impl<RS: Read + Seek> Xlsx<RS> {
     }
 
     /// Gets the worksheet merge cell dimensions
+    ///
+    /// The worksheet structure is cached, see [`prefetch_sheet`](Self::prefetch_sheet).
     pub fn worksheet_merge_cells(
         &mut self,
         name: &str,
     ) -> Option<Result<Vec<Dimensions>, XlsxError>> {
-        let (_, path) = self.sheets.iter().find(|(n, _)| n == name)?;
-        let xml = xml_reader(&mut self.zip, path);
-
-        xml.map(|xml| {
-            let mut xml = xml?;
-            let mut merge_cells = Vec::new();
-            let mut buffer = Vec::new();
-
-            loop {
-                buffer.clear();
+        if !self.sheets.iter().any(|(n, _)| n == name) {
+            return None;
+        }
+        match self.prefetch_sheet(name) {
+            Ok(()) => Some(Ok(self.sheet_structures[name].merge_cells.clone())),
+            Err(XlsxError::NotAWorksheet(_)) => Some(Ok(Vec::new())),
+            Err(XlsxError::WorksheetNotFound(_)) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
 
-                match xml.read_event_into(&mut buffer) {
-                    Ok(Event::Start(event)) if event.local_name().as_ref() == b"mergeCells" => {
-                        if let Ok(cells) = read_merge_cells(&mut xml) {
+    /// Read the structure of a worksheet (declared dimensions and merged cells) once
+    ///
+    /// The structure is cached and reused by [`worksheet_merge_cells`](Self::worksheet_merge_cells),
+    /// the methods based on it (such as [`load_merged_regions`](Self::load_merged_regions))
+    /// and [`worksheet_dimensions`](Self::worksheet_dimensions), so that the worksheet is not
+    /// decompressed and parsed again for each of them. Calling it again for the same sheet
+    /// does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`XlsxError::NotAWorksheet`] if the sheet has no cells (e.g. a chartsheet).
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/merge_cells.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// workbook.prefetch_sheet("Sheet1").unwrap();
+    /// // no xml is parsed anymore
+    /// let merges = workbook.worksheet_merge_cells("Sheet1").unwrap().unwrap();
+    /// assert_eq!(merges.len(), 3);
+    /// ```
+    pub fn prefetch_sheet(&mut self, name: &str) -> Result<(), XlsxError> {
+        if self.sheet_structures.contains_key(name) {
+            return Ok(());
+        }
+        let mut xml = self.worksheet_xml(name)?;
+        let mut dimensions = Dimensions::default();
+        let mut merge_cells = Vec::new();
+        let mut sh_type = None;
+        let mut has_cells = false;
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                    b"dimension" => {
+                        match get_attribute(e.attributes(), QName(b"ref"))?.map(get_dimension) {
+                            Some(Ok(dim)) => dimensions = dim,
+                            Some(Err(err)) => warn!("ignoring invalid dimension: {err}"),
+                            None => warn!("ignoring dimension without 'ref' attribute"),
+                        }
+                    }
+                    b"sheetData" => {
+                        has_cells = true;
+                        xml.read_to_end_into(e.name(), &mut Vec::new())?;
+                    }
+                    b"mergeCells" => {
+                        if let Ok(cells) = read_merge_cells(&mut xml, e) {
                             merge_cells = cells;
                         }
+                    }
+                    typ => {
+                        if sh_type.is_none() {
+                            sh_type = Some(xml.decoder().decode(typ)?.to_string());
+                        }
+                    }
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        drop(xml);
+        if !has_cells {
+            return Err(XlsxError::NotAWorksheet(sh_type.unwrap_or_default()));
+        }
+        self.sheet_structures.insert(
+            name.to_string(),
+            SheetStructure {
+                dimensions,
+                merge_cells,
+            },
+        );
+        Ok(())
+    }
 
-                        break;
+    /// Get the worksheet data along with the part each cell takes in merged regions
+    ///
+    /// The range is the one of [`worksheet_range`](Reader::worksheet_range): merged regions
+    /// are clipped to it, but the roles still hold the whole region dimensions.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Dimensions, MergeRole, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/merge_cells.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook.worksheet_range_with_merge_info("Sheet1").unwrap();
+    /// let merge = Dimensions::new((0, 0), (0, 1));
+    /// assert_eq!(range[(0, 0)].1, MergeRole::Master(merge));
+    /// assert_eq!(range[(0, 1)].1, MergeRole::Covered(merge));
+    /// ```
+    pub fn worksheet_range_with_merge_info(
+        &mut self,
+        name: &str,
+    ) -> Result<Range<(Data, MergeRole)>, XlsxError> {
+        let range = self.worksheet_range(name)?;
+        let merge_cells = self
+            .worksheet_merge_cells(name)
+            .transpose()?
+            .unwrap_or_default();
+        let Some((start, end)) = range.start().zip(range.end()) else {
+            return Ok(Range::empty());
+        };
+        let mut merged = Range::new(start, end);
+        for (row, col, value) in range.used_cells() {
+            let pos = (start.0 + row as u32, start.1 + col as u32);
+            merged.set_value(pos, (value.clone(), MergeRole::None));
+        }
+        for dims in merge_cells {
+            for row in dims.start.0.max(start.0)..=dims.end.0.min(end.0) {
+                for col in dims.start.1.max(start.1)..=dims.end.1.min(end.1) {
+                    if let Some(cell) = merged.get_value_mut((row, col)) {
+                        cell.1 = if (row, col) == dims.start {
+                            MergeRole::Master(dims)
+                        } else {
+                            MergeRole::Covered(dims)
+                        };
                     }
-                    Ok(Event::Eof) => break,
-                    Err(e) => return Err(XlsxError::Xml(e)),
-                    _ => (),
                 }
             }
-
-            Ok(merge_cells)
-        })
+        }
+        Ok(merged)
     }
 
     /// Get the nth worksheet. Shortcut for getting the nth
@@ -859,6 +1281,255 @@ impl<RS: Read + Seek> Xlsx<RS> {
     }
 }
 
+/// Page orientation of a worksheet
+///
+/// [ECMA-376 Part 1](https://www.ecma-international.org/publications-and-standards/standards/ecma-376/) 18.18.50
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Default orientation, usually portrait
+    #[default]
+    Default,
+    /// Portrait
+    Portrait,
+    /// Landscape
+    Landscape,
+}
+
+/// Page margins of a worksheet, in inches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageMargins {
+    /// Left margin
+    pub left: f64,
+    /// Right margin
+    pub right: f64,
+    /// Top margin
+    pub top: f64,
+    /// Bottom margin
+    pub bottom: f64,
+    /// Header margin
+    pub header: f64,
+    /// Footer margin
+    pub footer: f64,
+}
+
+impl Default for PageMargins {
+    fn default() -> Self {
+        PageMargins {
+            left: 0.7,
+            right: 0.7,
+            top: 0.75,
+            bottom: 0.75,
+            header: 0.3,
+            footer: 0.3,
+        }
+    }
+}
+
+/// Page setup of a worksheet
+///
+/// [ECMA-376 Part 1](https://www.ecma-international.org/publications-and-standards/standards/ecma-376/) 18.3.1.63
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageSetup {
+    /// Orientation
+    pub orientation: Orientation,
+    /// Paper size, as defined by ECMA-376 (1 is Letter, 9 is A4)
+    pub paper_size: u32,
+    /// Print scaling, in percent
+    pub scale: u32,
+    /// Whether the sheet is scaled to fit `fit_to_width` x `fit_to_height` pages
+    /// rather than using `scale`
+    pub fit_to_page: bool,
+    /// Number of pages horizontally when fitting to pages (0 is automatic)
+    pub fit_to_width: u32,
+    /// Number of pages vertically when fitting to pages (0 is automatic)
+    pub fit_to_height: u32,
+    /// Margins
+    pub margins: PageMargins,
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        PageSetup {
+            orientation: Orientation::Default,
+            paper_size: 1,
+            scale: 100,
+            fit_to_page: false,
+            fit_to_width: 1,
+            fit_to_height: 1,
+            margins: PageMargins::default(),
+        }
+    }
+}
+
+/// Header and footer texts of a worksheet
+///
+/// Texts keep their formatting codes (`&P`, `&D`, `&L`, ...) uninterpreted
+/// and are `None` when not defined.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HeaderFooter {
+    /// Header of odd pages (or of all pages)
+    pub odd_header: Option<String>,
+    /// Footer of odd pages (or of all pages)
+    pub odd_footer: Option<String>,
+    /// Header of even pages
+    pub even_header: Option<String>,
+    /// Footer of even pages
+    pub even_footer: Option<String>,
+}
+
+/// Protection of a worksheet and the operations it allows
+///
+/// Operations are only restricted when `protected` is `true`. The default value is an
+/// unprotected sheet allowing everything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetProtection {
+    /// Is the sheet protected
+    pub protected: bool,
+    /// Is a password (hash) set, the password itself cannot be read
+    pub has_password: bool,
+    /// Locked cells can be selected
+    pub select_locked_cells: bool,
+    /// Unlocked cells can be selected
+    pub select_unlocked_cells: bool,
+    /// Cells can be formatted
+    pub format_cells: bool,
+    /// Columns can be formatted
+    pub format_columns: bool,
+    /// Rows can be formatted
+    pub format_rows: bool,
+    /// Columns can be inserted
+    pub insert_columns: bool,
+    /// Rows can be inserted
+    pub insert_rows: bool,
+    /// Hyperlinks can be inserted
+    pub insert_hyperlinks: bool,
+    /// Columns can be deleted
+    pub delete_columns: bool,
+    /// Rows can be deleted
+    pub delete_rows: bool,
+    /// Ranges can be sorted
+    pub sort: bool,
+    /// Auto filters can be used
+    pub auto_filter: bool,
+    /// Pivot tables can be used
+    pub pivot_tables: bool,
+    /// Objects can be edited
+    pub edit_objects: bool,
+    /// Scenarios can be edited
+    pub edit_scenarios: bool,
+}
+
+impl Default for SheetProtection {
+    fn default() -> Self {
+        SheetProtection {
+            protected: false,
+            has_password: false,
+            select_locked_cells: true,
+            select_unlocked_cells: true,
+            format_cells: true,
+            format_columns: true,
+            format_rows: true,
+            insert_columns: true,
+            insert_rows: true,
+            insert_hyperlinks: true,
+            delete_columns: true,
+            delete_rows: true,
+            sort: true,
+            auto_filter: true,
+            pivot_tables: true,
+            edit_objects: true,
+            edit_scenarios: true,
+        }
+    }
+}
+
+/// Protection of the workbook structure and windows
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WorkbookProtection {
+    /// Sheets cannot be added, deleted, moved, renamed, hidden or unhidden
+    pub lock_structure: bool,
+    /// Workbook windows cannot be moved or resized
+    pub lock_windows: bool,
+    /// Is a password (hash) set, the password itself cannot be read
+    pub has_password: bool,
+}
+
+/// Outline (grouping) levels of the rows and columns of a worksheet
+///
+/// Only rows and columns with a non-zero level are stored, positions are 0 based.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OutlineLevels {
+    /// Outline level per row
+    pub rows: BTreeMap<u32, u8>,
+    /// Outline level per column
+    pub columns: BTreeMap<u32, u8>,
+}
+
+impl OutlineLevels {
+    /// Get the outline level of a row, 0 if not grouped
+    pub fn row_level(&self, row: u32) -> u8 {
+        self.rows.get(&row).copied().unwrap_or(0)
+    }
+
+    /// Get the outline level of a column, 0 if not grouped
+    pub fn column_level(&self, col: u32) -> u8 {
+        self.columns.get(&col).copied().unwrap_or(0)
+    }
+}
+
+/// A rich value (linked data type such as stocks or geography) attached to a cell
+///
+/// Values are kept as stored in `xl/richData/rdrichvalue.xml`, their keys being
+/// defined by the rich value structure.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RichValue {
+    /// Index of the rich value structure (in `xl/richData/rdrichvaluestructure.xml`)
+    pub structure: usize,
+    /// Values, in structure key order
+    pub values: Vec<String>,
+}
+
+/// The part a cell takes in a merged region
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MergeRole {
+    /// The cell is not merged
+    #[default]
+    None,
+    /// Top-left cell of the merged region, holding its value
+    Master(Dimensions),
+    /// Other cell of the merged region
+    Covered(Dimensions),
+}
+
+impl CellType for (Data, MergeRole) {}
+
+/// The cell references of a chart series
+///
+/// References are formulas such as `Sheet1!$B$2:$B$5`, as written in the chart.
+/// For scatter and bubble charts, categories are the x values and values the y values.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChartSeries {
+    /// Reference of the series name
+    pub name: Option<String>,
+    /// Reference of the categories
+    pub categories: Option<String>,
+    /// Reference of the values
+    pub values: Option<String>,
+}
+
+/// An external data connection of the workbook, from `xl/connections.xml`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DataConnection {
+    /// Connection name
+    pub name: String,
+    /// Connection type: 1 ODBC, 2 DAO, 3 file, 4 web query, 5 OLE DB, 6 text,
+    /// 7 ADO, 8 DSP
+    pub typ: u32,
+    /// Source of the data: the command (e.g. a SQL query) or else the connection
+    /// string of database connections, the url of web queries, the file of text imports
+    pub source: Option<String>,
+}
+
 struct TableMetadata {
     name: String,
     sheet_name: String,
@@ -887,52 +1558,1672 @@ impl InnerTableMetadata {
 }
 
 impl<RS: Read + Seek> Xlsx<RS> {
-    /// Get a reader over all used cells in the given worksheet cell reader
-    pub fn worksheet_cells_reader<'a>(
-        &'a mut self,
-        name: &str,
-    ) -> Result<XlsxCellReader<'a>, XlsxError> {
-        let (_, path) = self
-            .sheets
-            .iter()
-            .find(|&(n, _)| n == name)
-            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
-        let xml = xml_reader(&mut self.zip, path)
-            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))??;
-        let is_1904 = self.is_1904;
-        let strings = &self.strings;
-        let formats = &self.formats;
-        XlsxCellReader::new(xml, strings, formats, is_1904)
+    /// Get the number format code applied by a cell style
+    ///
+    /// `style_index` is the index of the style in the workbook cell formats (the `s`
+    /// attribute of a cell). Builtin formats are resolved to their default code, locale
+    /// dependent builtin formats return `None`.
+    ///
+    /// With `XlsxOptions::lazy_cell_parts`, styles are only available once loaded with
+    /// [`load_cell_parts`](Self::load_cell_parts) or by reading a worksheet.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/number_format_codes.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// assert_eq!(workbook.cell_format_string(1), Some("#,##0.00 \"EUR\""));
+    /// assert_eq!(workbook.cell_format_string(2), Some("0.00%"));
+    /// ```
+    pub fn cell_format_string(&self, style_index: usize) -> Option<&str> {
+        self.format_codes.get(style_index)?.as_deref()
     }
-}
 
-impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
-    type Error = XlsxError;
+    /// Get the theme major (headings) and minor (body) latin font names
+    ///
+    /// They are read from the `<a:fontScheme>` of `xl/theme/theme1.xml` and resolve
+    /// the "theme font" references of the cell styles.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let (major, minor) = workbook.theme_fonts().unwrap();
+    /// assert_eq!((major.as_str(), minor.as_str()), ("Cambria", "Calibri"));
+    /// ```
+    pub fn theme_fonts(&self) -> Option<(String, String)> {
+        self.theme_fonts.clone()
+    }
 
-    fn new(mut reader: RS) -> Result<Self, XlsxError> {
-        check_for_password_protected(&mut reader)?;
+    /// Get the workbook protection, if any
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/sheet_protection.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let protection = workbook.workbook_protection().unwrap();
+    /// assert!(protection.lock_structure);
+    /// ```
+    pub fn workbook_protection(&self) -> Option<WorkbookProtection> {
+        self.workbook_protection
+    }
 
-        let mut xlsx = Xlsx {
-            zip: ZipArchive::new(reader)?,
-            strings: Vec::new(),
-            formats: Vec::new(),
-            is_1904: false,
-            sheets: Vec::new(),
-            tables: None,
-            metadata: Metadata::default(),
-            #[cfg(feature = "picture")]
-            pictures: None,
-            merged_regions: None,
-            options: XlsxOptions::default(),
+    /// Get the cells containing formulas in calculation order, as `(sheet, row, col)`
+    ///
+    /// This is read from `xl/calcChain.xml`, an empty vector is returned if the
+    /// workbook has no calculation chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/calc_chain.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let chain = workbook.calc_chain().unwrap();
+    /// assert_eq!(chain[0], ("Calc".to_string(), 0, 1));
+    /// ```
+    pub fn calc_chain(&mut self) -> Result<Vec<(String, u32, u32)>, XlsxError> {
+        let sheet_ids = self.read_sheet_ids()?;
+        let mut xml = match xml_reader(&mut self.zip, "xl/calcChain.xml") {
+            None => return Ok(Vec::new()),
+            Some(x) => x?,
         };
-        xlsx.read_shared_strings()?;
-        xlsx.read_styles()?;
-        let relationships = xlsx.read_relationships()?;
-        xlsx.read_workbook(&relationships)?;
-        #[cfg(feature = "picture")]
-        xlsx.read_pictures()?;
-
-        Ok(xlsx)
+        let mut chain = Vec::new();
+        // the sheet id is omitted when it is the same as the previous cell one
+        let mut sheet = sheet_ids.first().map(|(_, name)| name.as_str());
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"c" => {
+                    if let Some(i) = get_attribute(e.attributes(), QName(b"i"))? {
+                        let id: u32 = xml.decoder().decode(i)?.parse()?;
+                        sheet = sheet_ids
+                            .iter()
+                            .find(|(sheet_id, _)| *sheet_id == id)
+                            .map(|(_, name)| name.as_str());
+                        if sheet.is_none() {
+                            warn!("calculation chain references unknown sheet id {id}");
+                        }
+                    }
+                    let Some(sheet) = sheet else { continue };
+                    if let Some(r) = get_attribute(e.attributes(), QName(b"r"))? {
+                        let (row, col) = get_row_column(r)?;
+                        chain.push((sheet.to_string(), row, col));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"calcChain" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(chain)
+    }
+
+    /// Read the `sheetId` and name of the sheets from `xl/workbook.xml`
+    fn read_sheet_ids(&mut self) -> Result<Vec<(u32, String)>, XlsxError> {
+        let mut xml = match xml_reader(&mut self.zip, "xl/workbook.xml") {
+            None => return Ok(Vec::new()),
+            Some(x) => x?,
+        };
+        let mut sheet_ids = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"sheet" => {
+                    let mut id = None;
+                    let mut name = None;
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        match a.key {
+                            QName(b"sheetId") => {
+                                id = Some(xml.decoder().decode(&a.value)?.parse()?)
+                            }
+                            QName(b"name") => {
+                                name = Some(a.decode_and_unescape_value(xml.decoder())?.to_string())
+                            }
+                            _ => (),
+                        }
+                    }
+                    if let (Some(id), Some(name)) = (id, name) {
+                        sheet_ids.push((id, name));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sheets" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(sheet_ids)
+    }
+
+    /// Get a shared string by its index, as referenced by cells of type `s`
+    ///
+    /// With `XlsxOptions::lazy_cell_parts`, shared strings are only available once loaded
+    /// with [`load_cell_parts`](Self::load_cell_parts) or by reading a worksheet.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// assert!(workbook.shared_string(0).is_some());
+    /// assert_eq!(workbook.shared_string(workbook.shared_string_count()), None);
+    /// ```
+    pub fn shared_string(&self, index: usize) -> Option<&str> {
+        self.strings.get(index).map(String::as_str)
+    }
+
+    /// Get the number of shared strings
+    ///
+    /// This is 0 until the shared strings are loaded, see [`shared_string`](Self::shared_string).
+    pub fn shared_string_count(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Get the series references of the charts of a chartsheet
+    ///
+    /// Chartsheets have no cells, this tells which worksheet cells their charts use.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`XlsxError::NotAChartsheet`] if the sheet is not a chartsheet.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/any_sheets.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let series = workbook.chartsheet_series("Chart").unwrap();
+    /// assert_eq!(series[1].values.as_deref(), Some("Visible!$B$1:$B$3"));
+    /// ```
+    pub fn chartsheet_series(&mut self, name: &str) -> Result<Vec<ChartSeries>, XlsxError> {
+        let path = self
+            .sheets
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, path)| path.clone())
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        let is_chartsheet = self
+            .metadata
+            .sheets
+            .iter()
+            .any(|s| s.name == name && s.typ == SheetType::ChartSheet);
+        if !is_chartsheet {
+            return Err(XlsxError::NotAChartsheet(name.into()));
+        }
+        let mut series = Vec::new();
+        for drawing in self.read_part_relationships(&path, "drawing")? {
+            for chart in self.read_part_relationships(&drawing, "chart")? {
+                if let Some(xml) = xml_reader(&mut self.zip, &chart) {
+                    series.extend(read_chart_series(xml?)?);
+                }
+            }
+        }
+        Ok(series)
+    }
+
+    /// Get the external data connections (queries, databases, web and text imports)
+    ///
+    /// Returns an empty list if the workbook has no connection.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/data_connections.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let connections = workbook.data_connections().unwrap();
+    /// assert_eq!(connections[0].name, "Sales");
+    /// assert_eq!(connections[0].source.as_deref(), Some("SELECT * FROM sales"));
+    /// ```
+    pub fn data_connections(&mut self) -> Result<Vec<DataConnection>, XlsxError> {
+        let mut xml = match xml_reader(&mut self.zip, "xl/connections.xml") {
+            None => return Ok(Vec::new()),
+            Some(x) => x?,
+        };
+        let mut connections = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                    b"connection" => {
+                        let mut connection = DataConnection::default();
+                        for a in e.attributes() {
+                            let a = a.map_err(XlsxError::XmlAttr)?;
+                            match a.key {
+                                QName(b"name") => {
+                                    connection.name =
+                                        a.decode_and_unescape_value(xml.decoder())?.into_owned()
+                                }
+                                QName(b"type") => {
+                                    connection.typ = xml.decoder().decode(&a.value)?.parse()?
+                                }
+                                _ => (),
+                            }
+                        }
+                        connections.push(connection);
+                    }
+                    pr @ (b"dbPr" | b"webPr" | b"textPr") => {
+                        let keys: &[&[u8]] = match pr {
+                            b"dbPr" => &[b"command", b"connection"],
+                            b"webPr" => &[b"url"],
+                            _ => &[b"sourceFile"],
+                        };
+                        let Some(connection) = connections.last_mut() else {
+                            continue;
+                        };
+                        for key in keys {
+                            if let Some(a) = e.try_get_attribute(*key)? {
+                                let source = a.decode_and_unescape_value(xml.decoder())?;
+                                connection.source = Some(source.into_owned());
+                                break;
+                            }
+                        }
+                    }
+                    _ => (),
+                },
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"connections" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(connections)
+    }
+
+    /// Get the path and raw content of the custom xml parts (`customXml/item1.xml`...)
+    ///
+    /// These parts hold data stored by add-ins or Power Query, their content is
+    /// returned as is. The related `itemProps` parts are not included.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/custom_xml.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let parts = workbook.custom_xml_parts().unwrap();
+    /// assert_eq!(parts[0].0, "customXml/item1.xml");
+    /// ```
+    pub fn custom_xml_parts(&mut self) -> Result<Vec<(String, Vec<u8>)>, XlsxError> {
+        let mut parts = Vec::new();
+        for i in 0..self.zip.len() {
+            let mut zfile = self.zip.by_index(i)?;
+            let zname = zfile.name().to_ascii_lowercase();
+            let is_item = zname
+                .strip_prefix("customxml/item")
+                .and_then(|n| n.strip_suffix(".xml"))
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+            if is_item {
+                let name = zfile.name().to_string();
+                let mut buf = Vec::new();
+                zfile.read_to_end(&mut buf)?;
+                parts.push((name, buf));
+            }
+        }
+        Ok(parts)
+    }
+
+    /// Get the content type of a part of the archive, as declared in `[Content_Types].xml`
+    ///
+    /// The part specific type is used if any, else the type associated to the part
+    /// extension. Paths are case insensitive and may start with `/`.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/picture.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// assert_eq!(workbook.content_type("xl/media/image2.png"), Some("image/png"));
+    /// ```
+    pub fn content_type(&self, path: &str) -> Option<&str> {
+        let path = path.trim_start_matches('/').to_ascii_lowercase();
+        if let Some(typ) = self.content_types.overrides.get(&path) {
+            return Some(typ);
+        }
+        let file_name = path.rsplit('/').next()?;
+        let (_, extension) = file_name.rsplit_once('.')?;
+        self.content_types
+            .defaults
+            .get(extension)
+            .map(String::as_str)
+    }
+
+    /// Run `f` with a xml reader over a part of the archive
+    ///
+    /// This is an advanced escape hatch to parse parts calamine doesn't support. The
+    /// reader is configured like the ones calamine uses internally (e.g. empty elements
+    /// are expanded into start and end events). It exposes `quick_xml` types, which may
+    /// change with any calamine release: consider this API unstable.
+    ///
+    /// Returns [`XlsxError::FileNotFound`] if the part doesn't exist.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    /// use quick_xml::events::Event;
+    ///
+    /// # let path = format!("{}/tests/any_sheets.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let sheets = workbook
+    ///     .with_part_reader("xl/workbook.xml", |xml| {
+    ///         let mut count = 0;
+    ///         let mut buf = Vec::new();
+    ///         loop {
+    ///             match xml.read_event_into(&mut buf)? {
+    ///                 Event::Start(e) if e.local_name().as_ref() == b"sheet" => count += 1,
+    ///                 Event::Eof => return Ok(count),
+    ///                 _ => (),
+    ///             }
+    ///             buf.clear();
+    ///         }
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(sheets, 4);
+    /// ```
+    pub fn with_part_reader<T, F>(&mut self, path: &str, f: F) -> Result<T, XlsxError>
+    where
+        F: FnOnce(&mut XlReader<'_>) -> Result<T, XlsxError>,
+    {
+        let path = path.trim_start_matches('/');
+        let mut xml = xml_reader(&mut self.zip, path)
+            .ok_or_else(|| XlsxError::FileNotFound(path.into()))??;
+        f(&mut xml)
+    }
+
+    /// Get the comment of the zip archive, if any
+    ///
+    /// Some tools tag files at the container level using this comment.
+    /// `None` is returned if the comment is empty or not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/archive_comment.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// assert_eq!(workbook.archive_comment(), Some("generated by pipeline v2"));
+    /// ```
+    pub fn archive_comment(&self) -> Option<&str> {
+        std::str::from_utf8(self.zip.comment())
+            .ok()
+            .filter(|c| !c.is_empty())
+    }
+
+    /// Resolve a defined name into the worksheet areas it covers
+    ///
+    /// Multi-area names (`Sheet1!$A$1:$B$2,Sheet2!$C$3`) return one entry per area.
+    /// Names referring to another name are followed. Returns `None` if the name doesn't
+    /// exist, if names refer to each other in a cycle or if the formula is not made of
+    /// cell references only (constants, functions, `#REF!`, whole rows or columns ...).
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Dimensions, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let areas = workbook.resolve_defined_name("MyDataTypes").unwrap();
+    /// assert_eq!(
+    ///     areas,
+    ///     vec![(
+    ///         "datatypes".to_string(),
+    ///         Dimensions { start: (0, 0), end: (5, 0) }
+    ///     )]
+    /// );
+    /// ```
+    pub fn resolve_defined_name(&self, name: &str) -> Option<Vec<(String, Dimensions)>> {
+        let mut visited = Vec::new();
+        let mut name = name;
+        loop {
+            if visited.contains(&name) {
+                warn!("defined name '{name}' is part of a reference cycle");
+                return None;
+            }
+            visited.push(name);
+            let (_, formula) = self.metadata.names.iter().find(|(n, _)| n == name)?;
+            if let Some(areas) = parse_area_references(formula) {
+                return Some(areas);
+            }
+            name = formula.trim().trim_start_matches('=');
+        }
+    }
+
+    /// Consume the workbook and return the underlying reader
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook_from_rs, Xlsx};
+    /// use std::io::Cursor;
+    ///
+    /// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let bytes = std::fs::read(path).unwrap();
+    /// let workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes.clone())).unwrap();
+    /// assert_eq!(workbook.into_inner().into_inner(), bytes);
+    /// ```
+    pub fn into_inner(self) -> RS {
+        self.zip.into_inner()
+    }
+
+    /// Get a xml reader over the given worksheet
+    fn worksheet_xml(&mut self, name: &str) -> Result<XlReader<'_>, XlsxError> {
+        let (_, path) = self
+            .sheets
+            .iter()
+            .find(|&(n, _)| n == name)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        xml_reader(&mut self.zip, path).ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?
+    }
+
+    /// Get the page setup of a worksheet (orientation, paper size, scaling and margins)
+    ///
+    /// Settings missing from the worksheet have their default values.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Orientation, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/page_setup.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let page_setup = workbook.worksheet_page_setup("Sheet1").unwrap();
+    /// assert_eq!(page_setup.orientation, Orientation::Landscape);
+    /// ```
+    pub fn worksheet_page_setup(&mut self, name: &str) -> Result<PageSetup, XlsxError> {
+        let mut xml = self.worksheet_xml(name)?;
+        let mut page_setup = PageSetup::default();
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"pageSetUpPr" => {
+                    if let Some(v) = get_attribute(e.attributes(), QName(b"fitToPage"))? {
+                        page_setup.fit_to_page = v == b"1" || v == b"true";
+                    }
+                }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"pageMargins" => {
+                    let margins = &mut page_setup.margins;
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        let margin = match a.key {
+                            QName(b"left") => &mut margins.left,
+                            QName(b"right") => &mut margins.right,
+                            QName(b"top") => &mut margins.top,
+                            QName(b"bottom") => &mut margins.bottom,
+                            QName(b"header") => &mut margins.header,
+                            QName(b"footer") => &mut margins.footer,
+                            _ => continue,
+                        };
+                        *margin = xml.decoder().decode(&a.value)?.parse()?;
+                    }
+                }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"pageSetup" => {
+                    for a in e.attributes() {
+                        match a.map_err(XlsxError::XmlAttr)? {
+                            Attribute {
+                                key: QName(b"orientation"),
+                                value: v,
+                            } => {
+                                page_setup.orientation = match &*v {
+                                    b"portrait" => Orientation::Portrait,
+                                    b"landscape" => Orientation::Landscape,
+                                    _ => Orientation::Default,
+                                }
+                            }
+                            Attribute {
+                                key: QName(b"paperSize"),
+                                value: v,
+                            } => page_setup.paper_size = xml.decoder().decode(&v)?.parse()?,
+                            Attribute {
+                                key: QName(b"scale"),
+                                value: v,
+                            } => page_setup.scale = xml.decoder().decode(&v)?.parse()?,
+                            Attribute {
+                                key: QName(b"fitToWidth"),
+                                value: v,
+                            } => page_setup.fit_to_width = xml.decoder().decode(&v)?.parse()?,
+                            Attribute {
+                                key: QName(b"fitToHeight"),
+                                value: v,
+                            } => page_setup.fit_to_height = xml.decoder().decode(&v)?.parse()?,
+                            _ => (),
+                        }
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"worksheet" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(page_setup)
+    }
+
+    /// Get the header and footer texts of a worksheet
+    ///
+    /// Formatting codes (such as `&P` for the page number) are left uninterpreted.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/page_setup.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let header_footer = workbook.worksheet_header_footer("Sheet1").unwrap();
+    /// assert_eq!(header_footer.odd_footer.as_deref(), Some("Page &P of &N"));
+    /// ```
+    pub fn worksheet_header_footer(&mut self, name: &str) -> Result<HeaderFooter, XlsxError> {
+        let mut xml = self.worksheet_xml(name)?;
+        let mut header_footer = HeaderFooter::default();
+        let mut buf = Vec::with_capacity(1024);
+        let mut val_buf = Vec::new();
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    let text = match e.local_name().as_ref() {
+                        b"oddHeader" => &mut header_footer.odd_header,
+                        b"oddFooter" => &mut header_footer.odd_footer,
+                        b"evenHeader" => &mut header_footer.even_header,
+                        b"evenFooter" => &mut header_footer.even_footer,
+                        _ => continue,
+                    };
+                    let mut value = String::new();
+                    loop {
+                        val_buf.clear();
+                        match xml.read_event_into(&mut val_buf)? {
+                            Event::Text(t) => value.push_str(&t.unescape()?),
+                            Event::End(end) if end.name() == e.name() => break,
+                            Event::Eof => return Err(XlsxError::XmlEof("headerFooter")),
+                            _ => (),
+                        }
+                    }
+                    *text = Some(value);
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"worksheet" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(header_footer)
+    }
+
+    /// Get the protection of a worksheet
+    ///
+    /// Sheets without a `sheetProtection` element are unprotected.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/sheet_protection.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let protection = workbook.worksheet_protection("Protected").unwrap();
+    /// assert!(protection.protected);
+    /// assert!(!protection.insert_rows);
+    /// ```
+    pub fn worksheet_protection(&mut self, name: &str) -> Result<SheetProtection, XlsxError> {
+        let mut xml = self.worksheet_xml(name)?;
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"sheetProtection" => {
+                    // attributes tell which operations are *locked*, most are by default
+                    let mut protection = SheetProtection {
+                        protected: false,
+                        has_password: false,
+                        select_locked_cells: true,
+                        select_unlocked_cells: true,
+                        format_cells: false,
+                        format_columns: false,
+                        format_rows: false,
+                        insert_columns: false,
+                        insert_rows: false,
+                        insert_hyperlinks: false,
+                        delete_columns: false,
+                        delete_rows: false,
+                        sort: false,
+                        auto_filter: false,
+                        pivot_tables: false,
+                        edit_objects: true,
+                        edit_scenarios: true,
+                    };
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        let locked = &*a.value == b"1" || &*a.value == b"true";
+                        let allowed = match a.key {
+                            QName(b"sheet") => {
+                                protection.protected = locked;
+                                continue;
+                            }
+                            QName(b"password") | QName(b"hashValue") => {
+                                protection.has_password = !a.value.is_empty();
+                                continue;
+                            }
+                            QName(b"selectLockedCells") => &mut protection.select_locked_cells,
+                            QName(b"selectUnlockedCells") => &mut protection.select_unlocked_cells,
+                            QName(b"formatCells") => &mut protection.format_cells,
+                            QName(b"formatColumns") => &mut protection.format_columns,
+                            QName(b"formatRows") => &mut protection.format_rows,
+                            QName(b"insertColumns") => &mut protection.insert_columns,
+                            QName(b"insertRows") => &mut protection.insert_rows,
+                            QName(b"insertHyperlinks") => &mut protection.insert_hyperlinks,
+                            QName(b"deleteColumns") => &mut protection.delete_columns,
+                            QName(b"deleteRows") => &mut protection.delete_rows,
+                            QName(b"sort") => &mut protection.sort,
+                            QName(b"autoFilter") => &mut protection.auto_filter,
+                            QName(b"pivotTables") => &mut protection.pivot_tables,
+                            QName(b"objects") => &mut protection.edit_objects,
+                            QName(b"scenarios") => &mut protection.edit_scenarios,
+                            _ => continue,
+                        };
+                        *allowed = !locked;
+                    }
+                    return Ok(protection);
+                }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"sheetData" => {
+                    // sheetProtection comes after sheetData, skip the cells
+                    xml.read_to_end_into(e.name(), &mut Vec::new())?;
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"worksheet" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(SheetProtection::default())
+    }
+
+    /// Get the outline (grouping) levels of the rows and columns of a worksheet
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/outline_levels.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let levels = workbook.worksheet_outline_levels("Sheet1").unwrap();
+    /// assert_eq!(levels.row_level(2), 2);
+    /// assert_eq!(levels.column_level(0), 0);
+    /// ```
+    pub fn worksheet_outline_levels(&mut self, name: &str) -> Result<OutlineLevels, XlsxError> {
+        let mut xml = self.worksheet_xml(name)?;
+        let mut levels = OutlineLevels::default();
+        let mut buf = Vec::with_capacity(1024);
+        // columns are declared before the rows
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"col" => {
+                    let (mut min, mut max, mut level) = (0, 0, 0);
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        match a.key {
+                            QName(b"min") => min = xml.decoder().decode(&a.value)?.parse()?,
+                            QName(b"max") => max = xml.decoder().decode(&a.value)?.parse()?,
+                            QName(b"outlineLevel") => {
+                                level = xml.decoder().decode(&a.value)?.parse()?
+                            }
+                            _ => (),
+                        }
+                    }
+                    if level > 0 && min > 0 {
+                        levels
+                            .columns
+                            .extend((min - 1..max.max(min)).map(|col| (col, level)));
+                    }
+                }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"sheetData" => break,
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"worksheet" => {
+                    return Ok(levels)
+                }
+                Ok(Event::Eof) => return Ok(levels),
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        read_rows(&mut xml, |row, e, decoder| {
+            if let Some(v) = get_attribute(e.attributes(), QName(b"outlineLevel"))? {
+                let level: u8 = decoder.decode(v)?.parse()?;
+                if level > 0 {
+                    levels.rows.insert(row, level);
+                }
+            }
+            Ok(())
+        })?;
+        Ok(levels)
+    }
+
+    /// Get the column span declared by each row of a worksheet, as
+    /// `(row, first_col, last_col)` (0 based)
+    ///
+    /// Spans come from the optional `spans` attribute of the rows and are only an
+    /// optimization hint written by Excel, rows without it are not returned.
+    /// They give a cheap view of the sheet structure without decoding the cells.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/row_spans.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let spans = workbook.worksheet_row_spans("Sheet1").unwrap();
+    /// assert_eq!(spans[0], (0, 0, 2));
+    /// ```
+    pub fn worksheet_row_spans(&mut self, name: &str) -> Result<Vec<(u32, u32, u32)>, XlsxError> {
+        let mut xml = self.worksheet_xml(name)?;
+        let mut spans = Vec::new();
+        read_rows(&mut xml, |row, e, decoder| {
+            if let Some(v) = get_attribute(e.attributes(), QName(b"spans"))? {
+                // a list of "first:last" 1 based column spans
+                let mut span: Option<(u32, u32)> = None;
+                for s in decoder.decode(v)?.split_whitespace() {
+                    let (first, last) = s.split_once(':').unwrap_or((s, s));
+                    let (first, last): (u32, u32) = (first.parse()?, last.parse()?);
+                    let (first, last) = (first.saturating_sub(1), last.saturating_sub(1));
+                    span = Some(match span {
+                        Some((f, l)) => (f.min(first), l.max(last)),
+                        None => (first, last),
+                    });
+                }
+                if let Some((first, last)) = span {
+                    spans.push((row, first, last));
+                }
+            }
+            Ok(())
+        })?;
+        Ok(spans)
+    }
+
+    /// Get the (0 based) rows of a worksheet marked as hidden, manually or by a filter
+    fn worksheet_hidden_rows(&mut self, name: &str) -> Result<BTreeSet<u32>, XlsxError> {
+        let mut xml = self.worksheet_xml(name)?;
+        let mut hidden = BTreeSet::new();
+        read_rows(&mut xml, |row, e, _| {
+            if matches!(
+                get_attribute(e.attributes(), QName(b"hidden"))?,
+                Some(b"1") | Some(b"true")
+            ) {
+                hidden.insert(row);
+            }
+            Ok(())
+        })?;
+        Ok(hidden)
+    }
+
+    /// Read worksheet data without its hidden rows
+    ///
+    /// Rows hidden manually or by an applied filter are removed and the following
+    /// rows are shifted up, the range still starts at the same position as
+    /// [`worksheet_range`](Reader::worksheet_range). This is what the user currently
+    /// sees in Excel.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Data, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/filtered_rows.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook.worksheet_visible_range("Sheet1").unwrap();
+    /// assert_eq!(range.height(), 4);
+    /// assert_eq!(range.get_value((2, 1)), Some(&Data::Float(3.)));
+    /// ```
+    pub fn worksheet_visible_range(&mut self, name: &str) -> Result<Range<Data>, XlsxError> {
+        let hidden = self.worksheet_hidden_rows(name)?;
+        let range = self.worksheet_range(name)?;
+        let (start, end) = match (range.start(), range.end()) {
+            (Some(start), Some(end)) if !hidden.is_empty() => (start, end),
+            _ => return Ok(range),
+        };
+        let mut inner = Vec::with_capacity(range.inner.len());
+        let mut height = 0;
+        for (row, cells) in (start.0..).zip(range.rows()) {
+            if !hidden.contains(&row) {
+                inner.extend_from_slice(cells);
+                height += 1;
+            }
+        }
+        if height == 0 {
+            return Ok(Range::default());
+        }
+        Ok(Range {
+            start,
+            end: (start.0 + height - 1, end.1),
+            inner,
+        })
+    }
+
+    /// Read the original text of the cells of a worksheet, before any value conversion
+    ///
+    /// This is the raw `<v>` content (e.g. `"007"` or `"1.50"` for numbers, `"1"` for booleans),
+    /// or the text of inline and shared strings. It is aligned with
+    /// [`worksheet_range`](Reader::worksheet_range) absolute positions and helps detecting
+    /// where parsing altered the representation.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issue3.xlsm", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let raw = workbook.worksheet_raw_text("Sheet1").unwrap();
+    /// assert_eq!(raw.get_value((0, 0)).map(String::as_str), Some("1"));
+    /// ```
+    pub fn worksheet_raw_text(&mut self, name: &str) -> Result<Range<String>, XlsxError> {
+        let mut cell_reader = match self.worksheet_cells_reader(name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(typ)) => {
+                warn!("'{typ}' not a worksheet");
+                return Ok(Range::default());
+            }
+            Err(e) => return Err(e),
+        };
+        let len = cell_reader.dimensions().len();
+        let mut cells = Vec::new();
+        if len < 100_000 {
+            cells.reserve(len as usize);
+        }
+        while let Some(cell) = cell_reader.next_raw_text()? {
+            if !cell.val.is_empty() {
+                cells.push(cell);
+            }
+        }
+        Ok(Range::from_sparse(cells))
+    }
+
+    /// Count the non-empty cells of a worksheet
+    ///
+    /// Cells are streamed and never stored, which is much cheaper than building the
+    /// whole range when only the count is needed.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// assert_eq!(workbook.worksheet_used_cell_count("issue2").unwrap(), 6);
+    /// ```
+    pub fn worksheet_used_cell_count(&mut self, name: &str) -> Result<usize, XlsxError> {
+        let mut cell_reader = match self.worksheet_cells_reader(name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(typ)) => {
+                warn!("'{typ}' not a worksheet");
+                return Ok(0);
+            }
+            Err(e) => return Err(e),
+        };
+        let mut count = 0;
+        while let Some(cell) = cell_reader.next_cell()? {
+            if cell.val != DataRef::Empty {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Get the formula of a single cell
+    ///
+    /// Cells are read in order until the target cell (absolute position) is reached,
+    /// so shared formulas are resolved while the rest of the worksheet is skipped.
+    /// Returns `None` if the cell has no formula.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issue_391.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let formula = workbook.cell_formula("Sheet1", 3, 0).unwrap();
+    /// assert_eq!(formula.as_deref(), Some("A3+1"));
+    /// ```
+    pub fn cell_formula(
+        &mut self,
+        name: &str,
+        row: u32,
+        col: u32,
+    ) -> Result<Option<String>, XlsxError> {
+        let mut cell_reader = match self.worksheet_cells_reader(name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(typ)) => {
+                warn!("'{typ}' not a worksheet");
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+        while let Some(cell) = cell_reader.next_formula()? {
+            if cell.pos == (row, col) {
+                return Ok(Some(cell.val).filter(|f| !f.is_empty()));
+            }
+            if cell.pos > (row, col) {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the last `n` non-empty rows of a worksheet
+    ///
+    /// The worksheet is still read from its start but only the last rows are kept in
+    /// memory, which is useful to tail large (e.g. append-only) sheets. Cells keep
+    /// their absolute positions.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook.worksheet_last_rows("issue2", 1).unwrap();
+    /// assert_eq!(range.height(), 1);
+    /// ```
+    pub fn worksheet_last_rows(&mut self, name: &str, n: usize) -> Result<Range<Data>, XlsxError> {
+        let mut cell_reader = match self.worksheet_cells_reader(name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(typ)) => {
+                warn!("'{typ}' not a worksheet");
+                return Ok(Range::default());
+            }
+            Err(e) => return Err(e),
+        };
+        if n == 0 {
+            return Ok(Range::default());
+        }
+        let mut rows: VecDeque<Vec<Cell<Data>>> = VecDeque::with_capacity(n.min(1024) + 1);
+        while let Some(cell) = cell_reader.next_cell()? {
+            if cell.val == DataRef::Empty {
+                continue;
+            }
+            let cell = Cell::new(cell.pos, Data::from(cell.val));
+            match rows.back_mut() {
+                Some(row) if row[0].pos.0 == cell.pos.0 => row.push(cell),
+                _ => {
+                    if rows.len() == n {
+                        rows.pop_front();
+                    }
+                    rows.push_back(vec![cell]);
+                }
+            }
+        }
+        Ok(Range::from_sparse(rows.into_iter().flatten().collect()))
+    }
+
+    /// Get the rich values (data types such as stocks or geography) linked to the cells
+    /// of a worksheet
+    ///
+    /// Cells reference their rich value through a value metadata index (`vm` attribute),
+    /// resolved via `xl/metadata.xml` and `xl/richData/rdrichvalue.xml`.
+    /// Cells without rich value are not returned.
+    pub fn worksheet_rich_values(&mut self, name: &str) -> Result<Vec<Cell<RichValue>>, XlsxError> {
+        let rich_values = self.read_rich_values()?;
+        let mut cell_reader = match self.worksheet_cells_reader(name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(typ)) => {
+                warn!("'{typ}' not a worksheet");
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e),
+        };
+        let mut cells = Vec::new();
+        while let Some(Cell { pos, val }) = cell_reader.next_value_metadata()? {
+            // `vm` is 1-based
+            if let Some(Some(rich_value)) = val.checked_sub(1).and_then(|i| rich_values.get(i)) {
+                cells.push(Cell::new(pos, rich_value.clone()));
+            }
+        }
+        Ok(cells)
+    }
+
+    /// Read the rich values of the workbook, indexed by value metadata record
+    fn read_rich_values(&mut self) -> Result<Vec<Option<RichValue>>, XlsxError> {
+        let mut xml = match xml_reader(&mut self.zip, "xl/metadata.xml") {
+            None => return Ok(Vec::new()),
+            Some(x) => x?,
+        };
+        let mut metadata_types = Vec::new();
+        // rich value index of each `XLRICHVALUE` future metadata block
+        let mut rich_value_blocks: Vec<Option<usize>> = Vec::new();
+        // (type, value) of the first record of each value metadata block
+        let mut value_metadata: Vec<Option<(usize, usize)>> = Vec::new();
+        let mut section = None;
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                    b"metadataType" => {
+                        let name = get_attribute(e.attributes(), QName(b"name"))?.unwrap_or(b"");
+                        metadata_types.push(name == b"XLRICHVALUE");
+                    }
+                    b"futureMetadata" => {
+                        let name = get_attribute(e.attributes(), QName(b"name"))?;
+                        if name == Some(b"XLRICHVALUE") {
+                            section = Some(b"futureMetadata".as_slice());
+                        }
+                    }
+                    b"valueMetadata" => section = Some(b"valueMetadata".as_slice()),
+                    b"bk" => match section {
+                        Some(b"futureMetadata") => rich_value_blocks.push(None),
+                        Some(b"valueMetadata") => value_metadata.push(None),
+                        _ => (),
+                    },
+                    b"rvb" if section == Some(b"futureMetadata") => {
+                        if let (Some(i), Some(last)) = (
+                            get_attribute(e.attributes(), QName(b"i"))?,
+                            rich_value_blocks.last_mut(),
+                        ) {
+                            *last = Some(xml.decoder().decode(i)?.parse()?);
+                        }
+                    }
+                    b"rc" if section == Some(b"valueMetadata") => {
+                        if let Some(last @ None) = value_metadata.last_mut() {
+                            let t = get_attribute(e.attributes(), QName(b"t"))?;
+                            let v = get_attribute(e.attributes(), QName(b"v"))?;
+                            if let (Some(t), Some(v)) = (t, v) {
+                                *last = Some((
+                                    xml.decoder().decode(t)?.parse()?,
+                                    xml.decoder().decode(v)?.parse()?,
+                                ));
+                            }
+                        }
+                    }
+                    _ => (),
+                },
+                Ok(Event::End(ref e))
+                    if matches!(
+                        e.local_name().as_ref(),
+                        b"futureMetadata" | b"valueMetadata"
+                    ) =>
+                {
+                    section = None
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"metadata" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+
+        drop(xml);
+
+        let mut rich_values = Vec::new();
+        if let Some(xml) = xml_reader(&mut self.zip, "xl/richData/rdrichvalue.xml") {
+            let mut xml = xml?;
+            let mut val_buf = Vec::new();
+            loop {
+                buf.clear();
+                match xml.read_event_into(&mut buf) {
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"rv" => {
+                        let structure = match get_attribute(e.attributes(), QName(b"s"))? {
+                            Some(s) => xml.decoder().decode(s)?.parse()?,
+                            None => 0,
+                        };
+                        rich_values.push(RichValue {
+                            structure,
+                            values: Vec::new(),
+                        });
+                    }
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"v" => {
+                        let mut value = String::new();
+                        loop {
+                            val_buf.clear();
+                            match xml.read_event_into(&mut val_buf)? {
+                                Event::Text(t) => value.push_str(&t.unescape()?),
+                                Event::End(end) if end.name() == e.name() => break,
+                                Event::Eof => return Err(XlsxError::XmlEof("v")),
+                                _ => (),
+                            }
+                        }
+                        if let Some(rv) = rich_values.last_mut() {
+                            rv.values.push(value);
+                        }
+                    }
+                    Ok(Event::End(ref e)) if e.local_name().as_ref() == b"rvData" => break,
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(value_metadata
+            .into_iter()
+            .map(|record| {
+                let (t, v) = record?;
+                // `t` is 1-based, `v` is 0-based
+                if !*metadata_types.get(t.checked_sub(1)?)? {
+                    return None;
+                }
+                let i = (*rich_value_blocks.get(v)?)?;
+                rich_values.get(i).cloned()
+            })
+            .collect())
+    }
+
+    /// Get a reader over all used cells in the given worksheet cell reader
+    pub fn worksheet_cells_reader<'a>(
+        &'a mut self,
+        name: &str,
+    ) -> Result<XlsxCellReader<'a>, XlsxError> {
+        self.load_cell_parts()?;
+        let (_, path) = self
+            .sheets
+            .iter()
+            .find(|&(n, _)| n == name)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        let xml = xml_reader(&mut self.zip, path)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))??;
+        let is_1904 = self.is_1904;
+        let strings = &self.strings;
+        let formats = &self.formats;
+        XlsxCellReader::new(xml, strings, formats, is_1904)
+    }
+
+    /// Iterate over the used cells of a worksheet with their number format code
+    ///
+    /// Each item is `(row, column, value, format)`, where `format` is the code of the
+    /// cell style as returned by [`cell_format_string`](Self::cell_format_string).
+    /// Cells are read in file order, without building a whole range. The iteration
+    /// stops after the first error. Use the `is_formula` method of the
+    /// [`worksheet_cells_reader`](Self::worksheet_cells_reader) reader to tell formula
+    /// results from literal values.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Data, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/number_format_codes.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let cell = workbook
+    ///     .worksheet_cells_formatted("Sheet1")
+    ///     .find(|c| matches!(c, Ok((0, 2, ..))))
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(cell, (0, 2, Data::Float(0.25), Some("0.00%".to_string())));
+    /// ```
+    pub fn worksheet_cells_formatted(
+        &mut self,
+        name: &str,
+    ) -> impl Iterator<Item = Result<(u32, u32, Data, Option<String>), XlsxError>> + '_ {
+        let loaded = self.load_cell_parts();
+        let format_codes = &self.format_codes;
+        let path = loaded.and_then(|()| {
+            self.sheets
+                .iter()
+                .find(|&(n, _)| n == name)
+                .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))
+        });
+        let reader = path.and_then(|(_, path)| match xml_reader(&mut self.zip, path) {
+            Some(Ok(xml)) => XlsxCellReader::new(xml, &self.strings, &self.formats, self.is_1904),
+            Some(Err(e)) => Err(e),
+            None => Err(XlsxError::WorksheetNotFound(name.into())),
+        });
+        let (mut reader, mut error) = match reader {
+            Ok(reader) => (Some(reader), None),
+            Err(e) => (None, Some(e)),
+        };
+        std::iter::from_fn(move || {
+            if let Some(e) = error.take() {
+                return Some(Err(e));
+            }
+            let cells = reader.as_mut()?;
+            match cells.next_cell() {
+                Ok(Some(cell)) => {
+                    let (row, col) = cell.get_position();
+                    let format = format_codes.get(cells.style_index()).cloned().flatten();
+                    Some(Ok((row, col, cell.get_value().clone().into(), format)))
+                }
+                Ok(None) => {
+                    reader = None;
+                    None
+                }
+                Err(e) => {
+                    reader = None;
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+
+    /// Read worksheet data as floats, without building a [`Data`] value per cell
+    ///
+    /// Numbers and dates (as their serial value) are read as is, any other cell,
+    /// empty ones included, is `NaN`. This is meant for large purely numeric sheets;
+    /// the reader options (header row, max rows...) apply as in `worksheet_range`.
+    /// Use [`worksheet_range_f64_strict`](Self::worksheet_range_f64_strict) to get an
+    /// error on non-numeric cells instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/1000_rows.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook.worksheet_range_f64("Sheet1").unwrap();
+    /// assert!(range[(0, 0)].is_nan()); // header
+    /// assert_eq!(range[(1, 0)], 1.0);
+    /// ```
+    pub fn worksheet_range_f64(&mut self, name: &str) -> Result<Range<f64>, XlsxError> {
+        self.read_worksheet_range_f64(name, false)
+    }
+
+    /// Read worksheet data as floats, failing on the first non-numeric cell
+    ///
+    /// As [`worksheet_range_f64`](Self::worksheet_range_f64) but a cell holding
+    /// anything else than a number or a date is an [`XlsxError::NotANumber`] error.
+    /// Missing and empty cells are still `NaN`.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx, XlsxError};
+    ///
+    /// # let path = format!("{}/tests/1000_rows.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let err = workbook.worksheet_range_f64_strict("Sheet1").unwrap_err();
+    /// assert!(matches!(err, XlsxError::NotANumber { row: 0, col: 0 })); // header
+    /// ```
+    pub fn worksheet_range_f64_strict(&mut self, name: &str) -> Result<Range<f64>, XlsxError> {
+        self.read_worksheet_range_f64(name, true)
+    }
+
+    fn read_worksheet_range_f64(
+        &mut self,
+        name: &str,
+        strict: bool,
+    ) -> Result<Range<f64>, XlsxError> {
+        let cells = self.read_worksheet_cells(name, |v| match v {
+            DataRef::Int(i) => Number::Float(i as f64),
+            DataRef::Float(f) => Number::Float(f),
+            DataRef::DateTime(d) => Number::Float(d.as_f64()),
+            _ => Number::NotANumber,
+        })?;
+        if strict {
+            if let Some(c) = cells.iter().find(|c| c.val == Number::NotANumber) {
+                let (row, col) = c.pos;
+                return Err(XlsxError::NotANumber { row, col });
+            }
+        }
+        Ok(Range::from_sparse_with(cells, f64::NAN, |n| match n {
+            Number::Float(f) => f,
+            Number::NotANumber => f64::NAN,
+        }))
+    }
+
+    /// Get the declared dimensions of a worksheet
+    ///
+    /// Only the `<dimension>` element at the start of the worksheet is read, no cell
+    /// is parsed. The dimensions are the ones written by the producing application
+    /// and may differ from the actual used range; they default to `A1` when missing.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Dimensions, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/declared_dimensions.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let dimensions = workbook.worksheet_dimensions("Sheet1").unwrap();
+    /// assert_eq!((dimensions.start, dimensions.end), ((0, 0), (4, 5)));
+    /// ```
+    pub fn worksheet_dimensions(&mut self, name: &str) -> Result<Dimensions, XlsxError> {
+        if let Some(structure) = self.sheet_structures.get(name) {
+            return Ok(structure.dimensions);
+        }
+        Ok(self.worksheet_cells_reader(name)?.dimensions())
+    }
+
+    /// Get the declared dimensions of all worksheets, in workbook order
+    ///
+    /// See [`worksheet_dimensions`](Self::worksheet_dimensions). Chartsheets, which have
+    /// no cells, are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/any_sheets.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let names: Vec<_> = workbook
+    ///     .all_dimensions()
+    ///     .unwrap()
+    ///     .into_iter()
+    ///     .map(|(name, _)| name)
+    ///     .collect();
+    /// assert_eq!(names, ["Visible", "Hidden", "VeryHidden"]);
+    /// ```
+    pub fn all_dimensions(&mut self) -> Result<Vec<(String, Dimensions)>, XlsxError> {
+        let names: Vec<_> = self.sheets.iter().map(|(name, _)| name.clone()).collect();
+        let mut dimensions = Vec::with_capacity(names.len());
+        for name in names {
+            match self.worksheet_dimensions(&name) {
+                Ok(dim) => dimensions.push((name, dim)),
+                Err(XlsxError::NotAWorksheet(_)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(dimensions)
+    }
+
+    /// Limit the number of rows read after the header row
+    ///
+    /// Reading stops as soon as a cell is found more than `max_rows` rows below the
+    /// header row, so the returned ranges contain the header row followed by at most
+    /// `max_rows` rows. This is useful to preview large worksheets.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Reader, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/1000_rows.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook.with_max_rows(5).worksheet_range("Sheet1").unwrap();
+    /// assert_eq!(range.height(), 6);
+    /// ```
+    pub fn with_max_rows(&mut self, max_rows: u32) -> &mut Self {
+        self.options.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Pad the returned ranges to the extent declared in the worksheet `<dimension>`
+    ///
+    /// By default, ranges only span the used cells. When enabled, ranges start and end
+    /// at the declared dimension, which gives a stable shape for fixed-schema sheets.
+    /// An explicit [`HeaderRow::Row`] and [`with_max_rows`](Self::with_max_rows) still
+    /// apply. Cells found outside the declared dimension are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Reader, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/declared_dimensions.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook
+    ///     .with_declared_dimensions(true)
+    ///     .worksheet_range("Sheet1")
+    ///     .unwrap();
+    /// assert_eq!(range.start(), Some((0, 0)));
+    /// assert_eq!(range.end(), Some((4, 5)));
+    /// ```
+    pub fn with_declared_dimensions(&mut self, declared_dimensions: bool) -> &mut Self {
+        self.options.declared_dimensions = declared_dimensions;
+        self
+    }
+
+    /// Stop reading worksheets at the first fully empty row after the data
+    ///
+    /// Empty rows before the first non-empty cell (or before an explicit
+    /// [`HeaderRow::Row`]) are not considered, so that only stray cells written
+    /// below a blank row, after the data, are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Reader, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/stop_at_empty_row.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook
+    ///     .with_stop_at_empty_row(true)
+    ///     .worksheet_range("Sheet1")
+    ///     .unwrap();
+    /// assert_eq!(range.end(), Some((4, 1)));
+    /// ```
+    pub fn with_stop_at_empty_row(&mut self, stop_at_empty_row: bool) -> &mut Self {
+        self.options.stop_at_empty_row = stop_at_empty_row;
+        self
+    }
+
+    /// Trim leading and trailing whitespace of string cells while reading, off by default
+    ///
+    /// Shared strings are borrowed as a trimmed slice, the shared string table itself
+    /// is left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Data, Reader, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/untrimmed_strings.xlsx", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook.with_trim_strings(true).worksheet_range("Sheet1").unwrap();
+    /// assert_eq!(range.get_value((0, 0)), Some(&Data::String("name".to_string())));
+    /// ```
+    pub fn with_trim_strings(&mut self, trim_strings: bool) -> &mut Self {
+        self.options.trim_strings = trim_strings;
+        self
+    }
+
+    /// Read worksheet data in corresponding worksheet path, converting each cell
+    /// to its display string
+    ///
+    /// This is more direct than reading a `Range<Data>` and mapping it afterwards
+    /// as shared strings are only cloned once.
+    ///
+    /// # Examples
+    /// ```
+    /// use calamine::{open_workbook, Xlsx};
+    ///
+    /// # let path = format!("{}/tests/issue3.xlsm", env!("CARGO_MANIFEST_DIR"));
+    /// let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    /// let range = workbook.worksheet_range_as_strings("Sheet1").unwrap();
+    /// assert_eq!(range.get_value((0, 1)).map(String::as_str), Some("a"));
+    /// ```
+    pub fn worksheet_range_as_strings(&mut self, name: &str) -> Result<Range<String>, XlsxError> {
+        self.read_worksheet_range(name, |v| v.to_string())
+    }
+
+    /// Read all non-empty cells of a worksheet, converting each value while reading
+    fn read_worksheet_range<'a, T, F>(&'a mut self, name: &str, f: F) -> Result<Range<T>, XlsxError>
+    where
+        T: CellType,
+        F: FnMut(DataRef<'a>) -> T,
+    {
+        Ok(Range::from_sparse(self.read_worksheet_cells(name, f)?))
+    }
+
+    /// Read the worksheet cells kept by the reader options, mapped with `f`
+    fn read_worksheet_cells<'a, T, F>(
+        &'a mut self,
+        name: &str,
+        mut f: F,
+    ) -> Result<Vec<Cell<T>>, XlsxError>
+    where
+        T: CellType,
+        F: FnMut(DataRef<'a>) -> T,
+    {
+        let header_row = self.options.header_row;
+        let max_rows = self.options.max_rows;
+        let declared_dimensions = self.options.declared_dimensions;
+        let stop_at_empty_row = self.options.stop_at_empty_row;
+        let trim_strings = self.options.trim_strings;
+        let mut f = |val| f(if trim_strings { trim_string(val) } else { val });
+        let mut cell_reader = match self.worksheet_cells_reader(name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(typ)) => {
+                log::warn!("'{typ}' not a valid worksheet");
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e),
+        };
+        let dimensions = cell_reader.dimensions();
+        let len = dimensions.len();
+        let mut cells: Vec<Cell<T>> = Vec::new();
+        if len < 100_000 {
+            cells.reserve(len as usize);
+        }
+        let mut stopped = false;
+
+        match header_row {
+            HeaderRow::FirstNonEmptyRow => {
+                // the header row is the row of the first non-empty cell
+                let mut last_row = None;
+                loop {
+                    match cell_reader.next_cell() {
+                        Ok(Some(Cell { pos, .. })) if last_row.is_some_and(|r| pos.0 > r) => break,
+                        Ok(Some(Cell { pos, .. }))
+                            if stop_at_empty_row
+                                && cells.last().is_some_and(|c| pos.0 > c.pos.0 + 1) =>
+                        {
+                            stopped = true;
+                            break;
+                        }
+                        Ok(Some(Cell {
+                            val: DataRef::Empty,
+                            ..
+                        })) => (),
+                        Ok(Some(Cell { pos, val })) => {
+                            if last_row.is_none() {
+                                last_row = max_rows.map(|n| pos.0.saturating_add(n));
+                            }
+                            cells.push(Cell::new(pos, f(val)))
+                        }
+                        Ok(None) => break,
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+            HeaderRow::Row(header_row_idx) => {
+                // If `header_row` is a row index, we only add non-empty cells after this index.
+                let last_row = max_rows.map(|n| header_row_idx.saturating_add(n));
+                loop {
+                    match cell_reader.next_cell() {
+                        Ok(Some(Cell { pos, .. })) if last_row.is_some_and(|r| pos.0 > r) => break,
+                        Ok(Some(Cell { pos, .. }))
+                            if stop_at_empty_row
+                                && cells.last().is_some_and(|c| pos.0 > c.pos.0 + 1) =>
+                        {
+                            stopped = true;
+                            break;
+                        }
+                        Ok(Some(Cell {
+                            val: DataRef::Empty,
+                            ..
+                        })) => (),
+                        Ok(Some(Cell { pos, val })) => {
+                            if pos.0 >= header_row_idx {
+                                cells.push(Cell::new(pos, f(val)));
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => return Err(e),
+                    }
+                }
+
+                // If `header_row` is set and the first non-empty cell is not at the `header_row`, we add
+                // an empty cell at the beginning with row `header_row` and same column as the first non-empty cell.
+                if cells.first().map_or(false, |c| c.pos.0 != header_row_idx) {
+                    cells.insert(
+                        0,
+                        Cell {
+                            pos: (
+                                header_row_idx,
+                                cells.first().expect("cells should not be empty").pos.1,
+                            ),
+                            val: T::default(),
+                        },
+                    );
+                }
+            }
+        }
+
+        if declared_dimensions {
+            let (first_row, last_row) = match header_row {
+                HeaderRow::FirstNonEmptyRow => {
+                    let header_row_idx = cells.first().map(|c| c.pos.0);
+                    let last_row = max_rows.and_then(|n| Some(header_row_idx?.saturating_add(n)));
+                    (dimensions.start.0, last_row)
+                }
+                HeaderRow::Row(idx) => (idx, max_rows.map(|n| idx.saturating_add(n))),
+            };
+            // do not pad over the empty row reading stopped at
+            let last_row = match cells.last() {
+                Some(c) if stopped => Some(last_row.map_or(c.pos.0, |r| r.min(c.pos.0))),
+                _ => last_row,
+            };
+            pad_to_dimensions(&mut cells, dimensions, first_row, last_row);
+        }
+
+        Ok(cells)
+    }
+}
+
+/// Trim the whitespace around string values, shared strings stay borrowed
+fn trim_string(value: DataRef<'_>) -> DataRef<'_> {
+    match value {
+        DataRef::String(s) if s.trim().len() != s.len() => DataRef::String(s.trim().to_string()),
+        DataRef::SharedString(s) => DataRef::SharedString(s.trim()),
+        v => v,
+    }
+}
+
+/// A cell read by `worksheet_range_f64`, defaulting to `NaN` so that missing cells are
+/// `NaN` too
+#[derive(Clone, PartialEq)]
+enum Number {
+    Float(f64),
+    NotANumber,
+}
+
+impl Default for Number {
+    fn default() -> Self {
+        Number::Float(f64::NAN)
+    }
+}
+
+impl CellType for Number {}
+
+/// Add empty cells at both corners of `dimensions` so that `Range::from_sparse`
+/// spans at least the declared extent, restricted to rows `first_row..=last_row`
+///
+/// Cells are sorted by row and later cells override earlier ones at the same position,
+/// so the padding cells are inserted where they don't break the order and are skipped
+/// when a cell already occupies their position.
+fn pad_to_dimensions<T: CellType>(
+    cells: &mut Vec<Cell<T>>,
+    dimensions: Dimensions,
+    first_row: u32,
+    last_row: Option<u32>,
+) {
+    let start = (dimensions.start.0.max(first_row), dimensions.start.1);
+    let mut end = dimensions.end;
+    if let Some(last_row) = last_row {
+        end.0 = end.0.min(last_row);
+    }
+    if start.0 > end.0 {
+        return;
+    }
+    if !cells.iter().any(|c| c.pos == start) {
+        let idx = match cells.first() {
+            Some(c) if c.pos.0 < start.0 => 1,
+            _ => 0,
+        };
+        cells.insert(idx, Cell::new(start, T::default()));
+    }
+    if !cells.iter().any(|c| c.pos == end) {
+        let idx = match cells.last() {
+            Some(c) if c.pos.0 >= end.0 => cells.len() - 1,
+            _ => cells.len(),
+        };
+        cells.insert(idx, Cell::new(end, T::default()));
+    }
+}
+
+impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
+    type Error = XlsxError;
+
+    fn new(reader: RS) -> Result<Self, XlsxError> {
+        Self::new_with_options(reader, XlsxOptions::default())
     }
 
     fn with_header_row(&mut self, header_row: HeaderRow) -> &mut Self {
@@ -1009,72 +3300,7 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
 
 impl<RS: Read + Seek> ReaderRef<RS> for Xlsx<RS> {
     fn worksheet_range_ref<'a>(&'a mut self, name: &str) -> Result<Range<DataRef<'a>>, XlsxError> {
-        let header_row = self.options.header_row;
-        let mut cell_reader = match self.worksheet_cells_reader(name) {
-            Ok(reader) => reader,
-            Err(XlsxError::NotAWorksheet(typ)) => {
-                log::warn!("'{typ}' not a valid worksheet");
-                return Ok(Range::default());
-            }
-            Err(e) => return Err(e),
-        };
-        let len = cell_reader.dimensions().len();
-        let mut cells = Vec::new();
-        if len < 100_000 {
-            cells.reserve(len as usize);
-        }
-
-        match header_row {
-            HeaderRow::FirstNonEmptyRow => {
-                // the header row is the row of the first non-empty cell
-                loop {
-                    match cell_reader.next_cell() {
-                        Ok(Some(Cell {
-                            val: DataRef::Empty,
-                            ..
-                        })) => (),
-                        Ok(Some(cell)) => cells.push(cell),
-                        Ok(None) => break,
-                        Err(e) => return Err(e),
-                    }
-                }
-            }
-            HeaderRow::Row(header_row_idx) => {
-                // If `header_row` is a row index, we only add non-empty cells after this index.
-                loop {
-                    match cell_reader.next_cell() {
-                        Ok(Some(Cell {
-                            val: DataRef::Empty,
-                            ..
-                        })) => (),
-                        Ok(Some(cell)) => {
-                            if cell.pos.0 >= header_row_idx {
-                                cells.push(cell);
-                            }
-                        }
-                        Ok(None) => break,
-                        Err(e) => return Err(e),
-                    }
-                }
-
-                // If `header_row` is set and the first non-empty cell is not at the `header_row`, we add
-                // an empty cell at the beginning with row `header_row` and same column as the first non-empty cell.
-                if cells.first().map_or(false, |c| c.pos.0 != header_row_idx) {
-                    cells.insert(
-                        0,
-                        Cell {
-                            pos: (
-                                header_row_idx,
-                                cells.first().expect("cells should not be empty").pos.1,
-                            ),
-                            val: DataRef::Empty,
-                        },
-                    );
-                }
-            }
-        }
-
-        Ok(Range::from_sparse(cells))
+        self.read_worksheet_range(name, |v| v)
     }
 }
 
@@ -1101,6 +3327,81 @@ fn xml_reader<'a, RS: Read + Seek>(
     }
 }
 
+/// Read the series references of a chart part
+fn read_chart_series(mut xml: XlReader<'_>) -> Result<Vec<ChartSeries>, XlsxError> {
+    let mut series = Vec::new();
+    let mut current: Option<ChartSeries> = None;
+    let mut field = None;
+    let mut buf = Vec::with_capacity(1024);
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"ser" => current = Some(ChartSeries::default()),
+                b"tx" | b"cat" | b"xVal" | b"val" | b"yVal" if current.is_some() => {
+                    field = Some(e.local_name().as_ref().to_vec());
+                }
+                b"f" => {
+                    let mut value = String::new();
+                    let mut val_buf = Vec::new();
+                    loop {
+                        val_buf.clear();
+                        match xml.read_event_into(&mut val_buf)? {
+                            Event::Text(t) => value.push_str(&t.unescape()?),
+                            Event::End(end) if end.local_name().as_ref() == b"f" => break,
+                            Event::Eof => return Err(XlsxError::XmlEof("f")),
+                            _ => (),
+                        }
+                    }
+                    if let (Some(ser), Some(field)) = (current.as_mut(), field.as_deref()) {
+                        match field {
+                            b"tx" => ser.name = Some(value),
+                            b"cat" | b"xVal" => ser.categories = Some(value),
+                            _ => ser.values = Some(value),
+                        }
+                    }
+                }
+                _ => (),
+            },
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"ser" => series.extend(current.take()),
+                b"tx" | b"cat" | b"xVal" | b"val" | b"yVal" => field = None,
+                _ => (),
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(XlsxError::Xml(e)),
+            _ => (),
+        }
+    }
+    Ok(series)
+}
+
+/// Resolves a relationship target into a path within the zip archive
+///
+/// Targets are relative to the folder of their source part, but generators also
+/// write absolute (`/xl/worksheets/sheet1.xml`) targets, targets already relative to
+/// the archive root (`xl/worksheets/sheet1.xml`) or with `.` and `..` segments.
+fn resolve_target(base_folder: &str, target: &str) -> String {
+    let target = target.replace('\\', "/");
+    let root = base_folder.split('/').next().unwrap_or_default();
+    let full_path = match target.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None if !root.is_empty() && target.starts_with(&format!("{}/", root)) => target,
+        None => format!("{}/{}", base_folder, target),
+    };
+    let mut segments = Vec::new();
+    for segment in full_path.split('/') {
+        match segment {
+            "" | "." => (),
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+    segments.join("/")
+}
+
 /// search through an Element's attributes for the named one
 pub(crate) fn get_attribute<'a>(
     atts: Attributes<'a>,
@@ -1135,8 +3436,11 @@ pub(crate) fn get_dimension(dimension: &[u8]) -> Result<Dimensions, XlsxError> {
             end: parts[0],
         }),
         2 => {
-            let rows = parts[1].0 - parts[0].0;
-            let columns = parts[1].1 - parts[0].1;
+            // corners may be given in any order
+            let start = (parts[0].0.min(parts[1].0), parts[0].1.min(parts[1].1));
+            let end = (parts[0].0.max(parts[1].0), parts[0].1.max(parts[1].1));
+            let rows = end.0 - start.0;
+            let columns = end.1 - start.1;
             if rows > MAX_ROWS {
                 warn!(
                     "xlsx has more than maximum number of rows ({} > {})",
@@ -1149,10 +3453,7 @@ pub(crate) fn get_dimension(dimension: &[u8]) -> Result<Dimensions, XlsxError> {
                     columns, MAX_COLUMNS
                 );
             }
-            Ok(Dimensions {
-                start: parts[0],
-                end: parts[1],
-            })
+            Ok(Dimensions { start, end })
         }
         len => Err(XlsxError::DimensionCount(len)),
     }
@@ -1221,6 +3522,46 @@ fn get_row_and_optional_column(range: &[u8]) -> Result<(u32, Option<u32>), XlsxE
     Ok((row, col.checked_sub(1)))
 }
 
+/// Parses a list of comma separated sheet qualified references
+/// (`Sheet1!$A$1:$B$2,'My Sheet'!$C$3`) into `(sheet, dimensions)`
+fn parse_area_references(formula: &str) -> Option<Vec<(String, Dimensions)>> {
+    let formula = formula.trim();
+    let formula = formula
+        .strip_prefix('(')
+        .and_then(|f| f.strip_suffix(')'))
+        .unwrap_or(formula);
+
+    // split areas on commas which are not part of a quoted sheet name
+    let mut areas = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in formula.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            ',' if !quoted => {
+                areas.push(&formula[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    areas.push(&formula[start..]);
+
+    areas
+        .into_iter()
+        .map(|area| {
+            let (sheet, reference) = area.trim().rsplit_once('!')?;
+            let sheet = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                Some(quoted) => quoted.replace("''", "'"),
+                None => sheet.to_string(),
+            };
+            let reference = reference.replace('$', "");
+            let dimensions = get_dimension(reference.as_bytes()).ok()?;
+            Some((sheet, dimensions))
+        })
+        .collect()
+}
+
 /// attempts to read either a simple or richtext string
 pub(crate) fn read_string(
     xml: &mut XlReader<'_>,
@@ -1287,11 +3628,79 @@ fn check_for_password_protected<RS: Read + Seek>(reader: &mut RS) -> Result<(),
     Ok(())
 }
 
-fn read_merge_cells(xml: &mut XlReader<'_>) -> Result<Vec<Dimensions>, XlsxError> {
-    let mut merge_cells = Vec::new();
+/// Check that a zip archive still has its end of central directory record
+///
+/// Zip archives start with a local file header and end with this record, a file
+/// starting like a zip without it was cut (e.g. an interrupted download). Other
+/// malformed files are left to the zip reader.
+fn check_for_truncated<RS: Read + Seek>(reader: &mut RS) -> Result<(), XlsxError> {
+    const LOCAL_FILE_HEADER: [u8; 4] = *b"PK\x03\x04";
+    const END_OF_CENTRAL_DIRECTORY: [u8; 4] = *b"PK\x05\x06";
+    // the record is 22 bytes long, followed by a comment of at most 65535 bytes
+    const MAX_RECORD_LEN: u64 = 22 + 65535;
+
+    let len = reader.seek(std::io::SeekFrom::End(0))?;
+    reader.seek(std::io::SeekFrom::Start(0))?;
+    let mut signature = [0; 4];
+    if len < 4 || reader.read_exact(&mut signature).is_err() || signature != LOCAL_FILE_HEADER {
+        reader.seek(std::io::SeekFrom::Start(0))?;
+        return Ok(());
+    }
+    let tail_len = len.min(MAX_RECORD_LEN);
+    reader.seek(std::io::SeekFrom::Start(len - tail_len))?;
+    let mut tail = Vec::with_capacity(tail_len as usize);
+    reader.take(tail_len).read_to_end(&mut tail)?;
+    reader.seek(std::io::SeekFrom::Start(0))?;
+    if tail.windows(4).any(|w| w == END_OF_CENTRAL_DIRECTORY) {
+        Ok(())
+    } else {
+        Err(XlsxError::Truncated)
+    }
+}
+
+/// Call `f` with the (0 based) index, the element and the decoder of each `<row>`,
+/// until the end of `<sheetData>`
+///
+/// Rows without an `r` attribute follow the previous one.
+fn read_rows<F>(xml: &mut XlReader<'_>, mut f: F) -> Result<(), XlsxError>
+where
+    F: FnMut(u32, &BytesStart<'_>, Decoder) -> Result<(), XlsxError>,
+{
+    let mut buf = Vec::with_capacity(1024);
+    let mut row_index = 0;
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"row" => {
+                if let Some(r) = get_attribute(e.attributes(), QName(b"r"))? {
+                    row_index = get_row(r)?;
+                }
+                f(row_index, e, xml.decoder())?;
+                row_index += 1;
+            }
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sheetData" => break,
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(XlsxError::Xml(e)),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Read the `<mergeCell>` children of a `<mergeCells>` element
+///
+/// The declared `count` of merges is only used to preallocate, it may be wrong.
+fn read_merge_cells(
+    xml: &mut XlReader<'_>,
+    merge_cells_element: &BytesStart<'_>,
+) -> Result<Vec<Dimensions>, XlsxError> {
+    let count = get_attribute(merge_cells_element.attributes(), QName(b"count"))?
+        .and_then(|c| std::str::from_utf8(c).ok()?.parse::<usize>().ok());
+    let mut merge_cells = Vec::with_capacity(count.unwrap_or(0).min(100_000));
+    let mut buffer = Vec::new();
 
     loop {
-        let mut buffer = Vec::new();
+        buffer.clear();
 
         match xml.read_event_into(&mut buffer) {
             Ok(Event::Start(event)) if event.local_name().as_ref() == b"mergeCell" => {
@@ -1407,6 +3816,21 @@ pub(crate) fn coordinate_to_name(cell: (u32, u32)) -> Result<Vec<u8>, XlsxError>
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resolve_target() {
+        let sheet = "xl/worksheets/sheet1.xml";
+        assert_eq!(resolve_target("xl", "worksheets/sheet1.xml"), sheet);
+        assert_eq!(resolve_target("xl", "./worksheets/sheet1.xml"), sheet);
+        assert_eq!(resolve_target("xl", "/xl/worksheets/sheet1.xml"), sheet);
+        assert_eq!(resolve_target("xl", "xl/worksheets/sheet1.xml"), sheet);
+        assert_eq!(resolve_target("xl", "worksheets\\sheet1.xml"), sheet);
+        assert_eq!(
+            resolve_target("xl/worksheets", "../tables/table1.xml"),
+            "xl/tables/table1.xml"
+        );
+        assert_eq!(resolve_target("", "sheet1.xml"), "sheet1.xml");
+    }
+
     #[test]
     fn test_dimensions() {
         assert_eq!(get_row_column(b"A1").unwrap(), (0, 0));
@@ -1427,6 +3851,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_area_references() {
+        assert_eq!(
+            parse_area_references("Sheet1!$A$1:$B$2,'My, ''Sheet'''!C3"),
+            Some(vec![
+                (
+                    "Sheet1".to_string(),
+                    Dimensions {
+                        start: (0, 0),
+                        end: (1, 1)
+                    }
+                ),
+                (
+                    "My, 'Sheet'".to_string(),
+                    Dimensions {
+                        start: (2, 2),
+                        end: (2, 2)
+                    }
+                ),
+            ])
+        );
+        assert_eq!(
+            parse_area_references("(Sheet1!$A$1,Sheet2!$D$4)")
+                .unwrap()
+                .iter()
+                .map(|(s, _)| s.as_str())
+                .collect::<Vec<_>>(),
+            ["Sheet1", "Sheet2"]
+        );
+        assert_eq!(parse_area_references("Sheet1!#REF!"), None);
+        assert_eq!(parse_area_references("Sheet1!$A:$A"), None);
+        assert_eq!(parse_area_references("42"), None);
+    }
+
     #[test]
     fn test_dimension_length() {
         assert_eq!(get_dimension(b"A1:Z99").unwrap().len(), 2_574);
@@ -1463,6 +3921,16 @@ mod tests {
             CellErrorType::from_str("#VALUE!").unwrap(),
             CellErrorType::Value
         );
+        assert_eq!(
+            CellErrorType::from_str("#SPILL!").unwrap(),
+            CellErrorType::Spill
+        );
+        assert_eq!(
+            CellErrorType::from_str("#CALC!").unwrap(),
+            CellErrorType::Calc
+        );
+        assert_eq!(CellErrorType::Spill.to_string(), "#SPILL!");
+        assert_eq!(CellErrorType::Calc.to_string(), "#CALC!");
     }
 
     #[test]
@@ -1,8 +1,11 @@
 use calamine::Data::{Bool, DateTime, DateTimeIso, DurationIso, Empty, Error, Float, Int, String};
 use calamine::{
-    open_workbook, open_workbook_auto, DataRef, DataType, Dimensions, ExcelDateTime,
-    ExcelDateTimeType, HeaderRow, Ods, Range, Reader, ReaderRef, Sheet, SheetType, SheetVisible,
-    Xls, Xlsb, Xlsx,
+    open_workbook, open_workbook_auto, open_workbook_auto_from_bytes, open_workbook_from_bytes,
+    open_workbook_from_slice, ChartSeries, DataRef, DataType, Dimensions, ExcelDateTime,
+    ExcelDateTimeType, HeaderFooter, HeaderRow, MergeRole, NumberSeparators, Ods, Orientation,
+    OutlineLevels, PageMargins, PageSetup, Range, Reader, ReaderRef, RichValue, Sheet,
+    SheetProtection, SheetType, SheetVisible, WorkbookProtection, Xls, Xlsb, XlsbError,
+    XlsbOptions, Xlsx, XlsxError, XlsxOptions,
 };
 use calamine::{CellErrorType::*, Data};
 use rstest::rstest;
@@ -353,6 +356,40 @@ fn defined_names_xlsb() {
     );
 }
 
+#[test]
+fn defined_names_xlsb_unsupported_formula() {
+    let mut excel: Xlsb<_> = wb("xlsb_unsupported_name.xlsb");
+    assert_eq!(
+        excel.defined_names(),
+        [
+            ("Answer".to_string(), "42".to_string()),
+            ("Exotic".to_string(), "".to_string()),
+            ("Unknown".to_string(), "".to_string()),
+        ]
+    );
+    assert!(excel.worksheet_range_at(0).unwrap().is_ok());
+}
+
+#[test]
+fn xlsb_worksheet_dimensions() {
+    let mut excel: Xlsb<_> = wb("issues.xlsb");
+    let dimensions = excel.worksheet_dimensions("issue6").unwrap();
+    assert_eq!(
+        dimensions,
+        Dimensions {
+            start: (0, 0),
+            end: (5, 0)
+        }
+    );
+    // the declared dimensions may be larger than the used range
+    let range = excel.worksheet_range("issue6").unwrap();
+    assert_eq!(range.end(), Some((3, 0)));
+    assert!(matches!(
+        excel.worksheet_dimensions("missing"),
+        Err(XlsbError::WorksheetNotFound(_))
+    ));
+}
+
 #[test]
 fn defined_names_xls() {
     let excel: Xls<_> = wb("issues.xls");
@@ -1864,6 +1901,30 @@ fn test_ref_xlsx() {
     );
 }
 
+#[test]
+fn date_xlsb_named_styles() {
+    // the named style (cell style xf) record must not shift cell formats
+    let mut xls: Xlsb<_> = wb("xlsb_named_styles.xlsb");
+    let range = xls.worksheet_range_at(0).unwrap().unwrap();
+
+    assert_eq!(
+        range.get_value((0, 0)),
+        Some(&DateTime(ExcelDateTime::new(
+            44197.0,
+            ExcelDateTimeType::DateTime,
+            false
+        )))
+    );
+    assert_eq!(
+        range.get_value((2, 0)),
+        Some(&DateTime(ExcelDateTime::new(
+            10.6320601851852,
+            ExcelDateTimeType::TimeDelta,
+            false
+        )))
+    );
+}
+
 #[test]
 fn test_ref_xlsb() {
     let mut excel: Xlsb<_> = wb("date.xlsb");
@@ -2138,3 +2199,2051 @@ fn test_string_ref() {
     // second sheet is the same with a cell reference to the first sheet
     range_eq!(xlsx.worksheet_range_at(1).unwrap().unwrap(), expected_range);
 }
+
+#[test]
+fn test_worksheet_range_as_strings() {
+    let mut xlsx: Xlsx<_> = wb("issues.xlsx");
+    let range = xlsx.worksheet_range_as_strings("issue2").unwrap();
+    range_eq!(range, [["1", "a"], ["2", "b"], ["3", "c"]]);
+
+    // same content as the `Data` range displayed
+    let expected = xlsx.worksheet_range("issue2").unwrap();
+    assert_eq!(range.start(), expected.start());
+    assert!(range
+        .cells()
+        .zip(expected.cells())
+        .all(|(s, d)| *s.2 == d.2.to_string()));
+}
+
+#[test]
+fn test_max_rows_xlsx() {
+    let mut xlsx: Xlsx<_> = wb("1000_rows.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.get_size(), (1000, 1));
+
+    let range = xlsx.with_max_rows(5).worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [String("value".to_string())],
+            [Float(1.)],
+            [Float(2.)],
+            [Float(3.)],
+            [Float(4.)],
+            [Float(5.)]
+        ]
+    );
+
+    // header row followed by 5 rows
+    let range = xlsx
+        .with_header_row(HeaderRow::Row(10))
+        .with_max_rows(5)
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((10, 0)));
+    assert_eq!(range.end(), Some((15, 0)));
+    assert_eq!(range.get_value((15, 0)), Some(&Float(15.)));
+}
+
+#[test]
+fn test_range_diff() {
+    let mut xlsx: Xlsx<_> = wb("issues.xlsx");
+    let old = xlsx.worksheet_range("issue2").unwrap();
+    assert!(old.diff(&old).is_empty());
+
+    let mut new = old.clone();
+    new.set_value((1, 1), String("changed".to_string()));
+    new.set_value((4, 0), Float(4.));
+    assert_eq!(
+        old.diff(&new),
+        vec![
+            (
+                1,
+                1,
+                Some(&String("b".to_string())),
+                Some(&String("changed".to_string()))
+            ),
+            (4, 0, None, Some(&Float(4.))),
+        ]
+    );
+}
+
+#[test]
+fn test_worksheet_page_setup() {
+    let mut xlsx: Xlsx<_> = wb("page_setup.xlsx");
+    let page_setup = xlsx.worksheet_page_setup("Sheet1").unwrap();
+    assert_eq!(page_setup.orientation, Orientation::Landscape);
+    assert_eq!(page_setup.paper_size, 9);
+    assert_eq!(page_setup.scale, 85);
+    assert!(page_setup.fit_to_page);
+    assert_eq!(page_setup.fit_to_width, 1);
+    assert_eq!(page_setup.fit_to_height, 0);
+    assert_eq!(
+        page_setup.margins,
+        PageMargins {
+            left: 0.25,
+            right: 0.25,
+            top: 0.5,
+            bottom: 0.5,
+            header: 0.1,
+            footer: 0.2,
+        }
+    );
+
+    let page_setup = xlsx.worksheet_page_setup("Sheet2").unwrap();
+    assert_eq!(page_setup, PageSetup::default());
+
+    assert!(xlsx.worksheet_page_setup("Missing").is_err());
+}
+
+#[test]
+fn test_worksheet_header_footer() {
+    let mut xlsx: Xlsx<_> = wb("page_setup.xlsx");
+    let header_footer = xlsx.worksheet_header_footer("Sheet1").unwrap();
+    assert_eq!(
+        header_footer,
+        HeaderFooter {
+            odd_header: Some("&C&\"Arial,Bold\"Report".to_string()),
+            odd_footer: Some("Page &P of &N".to_string()),
+            even_header: None,
+            even_footer: Some("&L&D".to_string()),
+        }
+    );
+
+    let header_footer = xlsx.worksheet_header_footer("Sheet2").unwrap();
+    assert_eq!(header_footer, HeaderFooter::default());
+}
+
+#[test]
+fn test_declared_dimensions_xlsx() {
+    let mut xlsx: Xlsx<_> = wb("declared_dimensions.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.start(), Some((1, 1)));
+    assert_eq!(range.end(), Some((2, 3)));
+
+    let range = xlsx
+        .with_declared_dimensions(true)
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((4, 5)));
+    assert_eq!(range.get_value((1, 1)), Some(&String("a".to_string())));
+    assert_eq!(range.get_value((2, 3)), Some(&Float(1.)));
+
+    let range = xlsx
+        .with_header_row(HeaderRow::FirstNonEmptyRow)
+        .with_max_rows(1)
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((2, 5)));
+
+    let range = xlsx
+        .with_header_row(HeaderRow::Row(2))
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((2, 0)));
+    assert_eq!(range.end(), Some((3, 5)));
+}
+
+#[test]
+fn test_declared_dimensions_value_on_corner() {
+    // the dimension under-reports the used range and a value sits on its end corner
+    let mut xlsx: Xlsx<_> = wb("under_reported_dimensions.xlsx");
+    let range = xlsx
+        .with_declared_dimensions(true)
+        .worksheet_range("Sheet1")
+        .unwrap();
+    range_eq!(
+        range,
+        [[Float(1.), Empty], [Empty, Float(5.)], [Float(1.), Empty]]
+    );
+}
+
+#[test]
+fn test_worksheet_rich_values() {
+    let mut xlsx: Xlsx<_> = wb("rich_values.xlsx");
+    let rich_values = xlsx.worksheet_rich_values("Sheet1").unwrap();
+    let rich_values: Vec<_> = rich_values
+        .iter()
+        .map(|c| (c.get_position(), c.get_value()))
+        .collect();
+    assert_eq!(
+        rich_values,
+        vec![
+            (
+                (0, 0),
+                &RichValue {
+                    structure: 0,
+                    values: vec!["France".to_string(), "Paris".to_string()],
+                }
+            ),
+            (
+                (2, 1),
+                &RichValue {
+                    structure: 1,
+                    values: vec!["MSFT".to_string(), "420.5".to_string()],
+                }
+            ),
+        ]
+    );
+    assert!(xlsx.worksheet_rich_values("Sheet2").unwrap().is_empty());
+
+    // rich value cells still read as their cached value
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.get_value((0, 0)), Some(&Error(Value)));
+}
+
+#[test]
+fn test_error_partial_eq() {
+    let path = format!("{}/tests/pass_protected.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let err = open_workbook::<Xlsx<_>, _>(path).err();
+    assert_eq!(err, Some(calamine::XlsxError::Password));
+
+    let path = format!("{}/tests/pass_protected.xlsb", env!("CARGO_MANIFEST_DIR"));
+    let err = open_workbook::<Xlsb<_>, _>(path).err();
+    assert_eq!(err, Some(calamine::XlsbError::Password));
+
+    let path = format!("{}/tests/issue_385.xls", env!("CARGO_MANIFEST_DIR"));
+    let err = open_workbook::<Xls<_>, _>(path).err();
+    assert_eq!(err, Some(calamine::XlsError::Password));
+
+    let path = format!("{}/tests/pass_protected.ods", env!("CARGO_MANIFEST_DIR"));
+    let err = open_workbook::<Ods<_>, _>(path).err();
+    assert_eq!(err, Some(calamine::OdsError::Password));
+
+    let mut xlsx: Xlsx<_> = wb("issues.xlsx");
+    let err = xlsx.worksheet_range("Missing").unwrap_err();
+    assert_eq!(
+        err,
+        calamine::XlsxError::WorksheetNotFound("Missing".into())
+    );
+    assert_ne!(err, calamine::XlsxError::WorksheetNotFound("Other".into()));
+
+    // io errors are compared by kind
+    let path = format!("{}/tests/missing.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let err = open_workbook::<Xlsx<_>, _>(path).err();
+    let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+    assert_eq!(err, Some(calamine::XlsxError::Io(not_found)));
+}
+
+#[test]
+fn test_unified_error() {
+    fn first_sheet<R>(path: &str) -> Result<Range<Data>, calamine::Error>
+    where
+        R: Reader<BufReader<File>>,
+        calamine::Error: From<R::Error>,
+    {
+        let path = format!("{}/tests/{}", env!("CARGO_MANIFEST_DIR"), path);
+        let mut workbook: R = open_workbook(path)?;
+        let name = workbook.sheet_names()[0].clone();
+        Ok(workbook.worksheet_range(&name)?)
+    }
+
+    assert!(first_sheet::<Xlsx<_>>("issues.xlsx").is_ok());
+    assert!(first_sheet::<Xlsb<_>>("issues.xlsb").is_ok());
+    assert!(first_sheet::<Xls<_>>("issues.xls").is_ok());
+    assert!(first_sheet::<Ods<_>>("issues.ods").is_ok());
+    assert_eq!(
+        first_sheet::<Xlsx<_>>("pass_protected.xlsx").unwrap_err(),
+        calamine::Error::Xlsx(calamine::XlsxError::Password)
+    );
+
+    // unsupported methods return an error rather than panicking
+    let path = format!("{}/tests/issues.ods", env!("CARGO_MANIFEST_DIR"));
+    let mut sheets = open_workbook_auto(path).unwrap();
+    assert!(sheets.worksheet_range_ref("datatypes").is_err());
+}
+
+#[test]
+fn test_open_workbook_from_bytes() {
+    let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let bytes = std::fs::read(path).unwrap();
+    let expected = wb::<Xlsx<_>>("issues.xlsx")
+        .worksheet_range("issue2")
+        .unwrap();
+
+    let mut xlsx: Xlsx<_> = open_workbook_from_slice(&bytes).unwrap();
+    assert!(xlsx
+        .worksheet_range("issue2")
+        .unwrap()
+        .content_eq(&expected));
+
+    let mut xlsx: Xlsx<_> = open_workbook_from_bytes(bytes).unwrap();
+    assert!(xlsx
+        .worksheet_range("issue2")
+        .unwrap()
+        .content_eq(&expected));
+}
+
+#[rstest]
+#[case("issues.xls")]
+#[case("issues.xlsx")]
+#[case("issues.xlsb")]
+#[case("issues.ods")]
+fn test_open_workbook_auto_from_bytes(#[case] file: &str) {
+    let path = format!("{}/tests/{}", env!("CARGO_MANIFEST_DIR"), file);
+    let bytes = std::fs::read(&path).unwrap();
+    let mut expected = open_workbook_auto(&path).unwrap();
+    let mut workbook = open_workbook_auto_from_bytes(&bytes).unwrap();
+    assert_eq!(workbook.sheet_names(), expected.sheet_names());
+    let name = workbook.sheet_names()[0].clone();
+    assert!(workbook
+        .worksheet_range(&name)
+        .unwrap()
+        .content_eq(&expected.worksheet_range(&name).unwrap()));
+}
+
+#[rstest]
+#[case("issues.xls")]
+#[case("issues.xlsx")]
+#[case("issues.xlsb")]
+#[case("issues.ods")]
+fn test_sheet_count_and_name_at(#[case] file: &str) {
+    let path = format!("{}/tests/{}", env!("CARGO_MANIFEST_DIR"), file);
+    let workbook = open_workbook_auto(path).unwrap();
+    let names = workbook.sheet_names();
+    assert_eq!(workbook.sheet_count(), names.len());
+    for (i, name) in names.iter().enumerate() {
+        assert_eq!(workbook.sheet_name_at(i), Some(name.as_str()));
+    }
+    assert_eq!(workbook.sheet_name_at(names.len()), None);
+}
+
+#[test]
+fn test_worksheet_ranges() {
+    let mut xlsx: Xlsx<_> = wb("issues.xlsx");
+    let names = xlsx.sheet_names();
+    let expected = xlsx.worksheets();
+
+    let mut ranges = xlsx.worksheet_ranges();
+    assert_eq!(ranges.len(), names.len());
+    let first = ranges.next().unwrap();
+    assert_eq!(ranges.len(), names.len() - 1);
+    let ranges: Vec<_> = std::iter::once(first).chain(ranges).collect();
+    assert_eq!(
+        ranges.iter().map(|(n, _)| n.clone()).collect::<Vec<_>>(),
+        names
+    );
+    for ((name, range), (expected_name, expected_range)) in ranges.iter().zip(&expected) {
+        assert_eq!(name, expected_name);
+        assert!(range.as_ref().unwrap().content_eq(expected_range));
+    }
+}
+
+#[test]
+fn test_range_columns() {
+    let range: Range<Data> = Range::empty();
+    assert_eq!(range.columns().len(), 0);
+    assert!(range.columns().next().is_none());
+
+    let mut range: Range<Data> = Range::new((1, 1), (3, 2));
+    range.set_value((1, 1), Int(1));
+    range.set_value((3, 2), Int(6));
+    let mut columns = range.columns();
+    assert_eq!(columns.len(), 2);
+    assert_eq!(
+        columns.next().unwrap().collect::<Vec<_>>(),
+        vec![&Int(1), &Empty, &Empty]
+    );
+    assert_eq!(columns.len(), 1);
+    assert_eq!(
+        columns.next().unwrap().collect::<Vec<_>>(),
+        vec![&Empty, &Empty, &Int(6)]
+    );
+    assert!(columns.next().is_none());
+}
+
+#[test]
+fn test_range_cells_by_column() {
+    let range: Range<Data> = Range::empty();
+    assert_eq!(range.cells_by_column().count(), 0);
+
+    let mut range: Range<Data> = Range::new((1, 1), (2, 3));
+    range.set_value((1, 2), Int(1));
+    range.set_value((2, 1), Int(2));
+    let cells = range.cells_by_column();
+    assert_eq!(cells.len(), 6);
+    assert_eq!(
+        cells.collect::<Vec<_>>(),
+        vec![
+            (0, 0, &Empty),
+            (1, 0, &Int(2)),
+            (0, 1, &Int(1)),
+            (1, 1, &Empty),
+            (0, 2, &Empty),
+            (1, 2, &Empty),
+        ]
+    );
+}
+
+#[test]
+fn test_range_row_windows() {
+    let mut range: Range<Data> = Range::new((0, 0), (9, 1));
+    for row in 0..10 {
+        range.set_value((row, 0), Int(row as i64));
+    }
+    let windows: Vec<Vec<&[Data]>> = range.row_windows(3).collect();
+    assert_eq!(
+        windows.iter().map(|w| w.len()).collect::<Vec<_>>(),
+        vec![3, 3, 3, 1]
+    );
+    assert_eq!(windows[1][0], &[Int(3), Empty]);
+    assert_eq!(windows[3][0], &[Int(9), Empty]);
+
+    let range: Range<Data> = Range::empty();
+    assert_eq!(range.row_windows(3).count(), 0);
+}
+
+#[test]
+fn test_number_separators_ods() {
+    let mut ods: Ods<_> = wb("text_numbers.ods");
+    let range = ods.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.get_value((0, 0)), Some(&String("1.234,56".into())));
+
+    ods.with_number_separators(NumberSeparators::new(',', Some('.')));
+    let range = ods.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [Float(1234.56), Float(-0.5), String("abc".into())],
+            [
+                String("12.34.5".into()),
+                Float(2.),
+                String("1,234.56".into())
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_number_separators_xls() {
+    let mut xls: Xls<_> = wb("xls_ref_String.xls");
+    let range = xls.worksheet_range("Sheet2").unwrap();
+    assert_eq!(range.get_value((0, 0)), Some(&String("88".into())));
+
+    xls.with_number_separators(NumberSeparators::new(',', Some('.')));
+    let range = xls.worksheet_range("Sheet2").unwrap();
+    assert_eq!(range.get_value((0, 0)), Some(&Float(88.)));
+    let range = xls.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.get_value((0, 0)), Some(&String("aa".into())));
+    let sheets = xls.worksheets();
+    assert_eq!(sheets[1].1.get_value((0, 0)), Some(&Float(88.)));
+}
+
+#[test]
+fn test_worksheet_raw_text() {
+    let mut xlsx: Xlsx<_> = wb("raw_text.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.get_value((0, 0)), Some(&Float(7.)));
+    assert_eq!(range.get_value((0, 1)), Some(&Float(1.5)));
+
+    let raw = xlsx.worksheet_raw_text("Sheet1").unwrap();
+    assert_eq!(raw.start(), range.start());
+    assert_eq!(raw.end(), range.end());
+    let raw: Vec<Vec<&str>> = raw
+        .rows()
+        .map(|r| r.iter().map(|s| s.as_str()).collect())
+        .collect();
+    assert_eq!(
+        raw,
+        vec![
+            vec!["007", "1.50", "shared", "inline", "1"],
+            vec!["14", "shared", "", "", ""]
+        ]
+    );
+}
+
+#[test]
+fn test_macro_sheet_xltm() {
+    let path = format!("{}/tests/macro_sheet.xltm", env!("CARGO_MANIFEST_DIR"));
+    let mut workbook = open_workbook_auto(path).unwrap();
+    assert_eq!(
+        workbook.sheets_metadata(),
+        &[
+            Sheet {
+                name: "Sheet1".to_string(),
+                typ: SheetType::WorkSheet,
+                visible: SheetVisible::Visible
+            },
+            Sheet {
+                name: "Macro1".to_string(),
+                typ: SheetType::MacroSheet,
+                visible: SheetVisible::Visible
+            },
+        ]
+    );
+    let range = workbook.worksheet_range("Macro1").unwrap();
+    range_eq!(range, [[String("Macro1".to_string())], [Bool(true)]]);
+}
+
+#[test]
+fn test_shared_string_out_of_range() {
+    let mut xlsx: Xlsx<_> = wb("shared_string_out_of_range.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    range_eq!(range, [[String("a".to_string()), Error(GettingData)]]);
+}
+
+#[test]
+fn test_cell_format_string() {
+    let excel: Xlsx<_> = wb("number_format_codes.xlsx");
+    assert_eq!(excel.cell_format_string(0), Some("General"));
+    assert_eq!(excel.cell_format_string(1), Some("#,##0.00 \"EUR\""));
+    assert_eq!(excel.cell_format_string(2), Some("0.00%"));
+    assert_eq!(excel.cell_format_string(3), Some("mm-dd-yy"));
+    assert_eq!(excel.cell_format_string(4), None);
+}
+
+#[test]
+fn test_worksheet_cells_formatted() {
+    let mut excel: Xlsx<_> = wb("number_format_codes.xlsx");
+    let cells = excel
+        .worksheet_cells_formatted("Sheet1")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        cells,
+        vec![
+            (0, 0, Float(1.), Some("General".to_string())),
+            (0, 1, Float(1234.5), Some("#,##0.00 \"EUR\"".to_string())),
+            (0, 2, Float(0.25), Some("0.00%".to_string())),
+            (
+                0,
+                3,
+                DateTime(ExcelDateTime::new(
+                    45000.,
+                    ExcelDateTimeType::DateTime,
+                    false
+                )),
+                Some("mm-dd-yy".to_string())
+            ),
+        ]
+    );
+
+    let mut cells = excel.worksheet_cells_formatted("Missing");
+    assert!(matches!(
+        cells.next(),
+        Some(Err(XlsxError::WorksheetNotFound(_)))
+    ));
+    assert!(cells.next().is_none());
+}
+
+#[test]
+fn test_resolve_defined_name() {
+    let excel: Xlsx<_> = wb("issues.xlsx");
+    assert_eq!(
+        excel.resolve_defined_name("OneRange"),
+        Some(vec![(
+            "Sheet1".to_string(),
+            Dimensions {
+                start: (0, 0),
+                end: (0, 0)
+            }
+        )])
+    );
+    assert_eq!(excel.resolve_defined_name("MyBrokenRange"), None);
+    assert_eq!(excel.resolve_defined_name("Missing"), None);
+}
+
+#[test]
+fn test_worksheet_protection() {
+    let mut excel: Xlsx<_> = wb("sheet_protection.xlsx");
+
+    let protection = excel.worksheet_protection("Protected").unwrap();
+    assert_eq!(
+        protection,
+        SheetProtection {
+            protected: true,
+            has_password: true,
+            select_locked_cells: false,
+            select_unlocked_cells: true,
+            format_cells: false,
+            format_columns: true,
+            format_rows: false,
+            insert_columns: false,
+            insert_rows: false,
+            insert_hyperlinks: false,
+            delete_columns: false,
+            delete_rows: false,
+            sort: true,
+            auto_filter: false,
+            pivot_tables: false,
+            edit_objects: false,
+            edit_scenarios: false,
+        }
+    );
+
+    let protection = excel.worksheet_protection("NoPassword").unwrap();
+    assert!(protection.protected);
+    assert!(!protection.has_password);
+    assert!(protection.insert_rows);
+    assert!(!protection.delete_rows);
+
+    assert_eq!(
+        excel.worksheet_protection("Unprotected").unwrap(),
+        SheetProtection::default()
+    );
+}
+
+#[test]
+fn test_workbook_protection() {
+    let excel: Xlsx<_> = wb("sheet_protection.xlsx");
+    assert_eq!(
+        excel.workbook_protection(),
+        Some(WorkbookProtection {
+            lock_structure: true,
+            lock_windows: false,
+            has_password: true,
+        })
+    );
+
+    let excel: Xlsx<_> = wb("issues.xlsx");
+    assert_eq!(excel.workbook_protection(), None);
+}
+
+#[test]
+fn test_worksheet_last_rows() {
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+
+    let range = excel.worksheet_last_rows("issue2", 2).unwrap();
+    assert_eq!(range.start(), Some((1, 0)));
+    range_eq!(
+        range,
+        [
+            [Float(2.), String("b".to_string())],
+            [Float(3.), String("c".to_string())]
+        ]
+    );
+
+    // more rows than available
+    let range = excel.worksheet_last_rows("spc_chrs", 100).unwrap();
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((7, 0)));
+
+    assert!(excel.worksheet_last_rows("issue2", 0).unwrap().is_empty());
+    assert!(excel.worksheet_last_rows("missing", 1).is_err());
+}
+
+#[test]
+fn test_worksheet_used_cell_count() {
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+    for name in excel.sheet_names() {
+        let count = excel.worksheet_used_cell_count(&name).unwrap();
+        let range = excel.worksheet_range(&name).unwrap();
+        assert_eq!(count, range.used_cells().count(), "{name}");
+    }
+    assert!(excel.worksheet_used_cell_count("missing").is_err());
+}
+
+#[test]
+fn test_deserialize_with_header_rows() {
+    use calamine::{Dimensions, RangeDeserializerBuilder};
+    use serde_derive::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        #[serde(rename = "Region")]
+        region: std::string::String,
+        #[serde(rename = "Sales / Q1")]
+        sales_q1: f64,
+        #[serde(rename = "Sales / Q2")]
+        sales_q2: f64,
+        #[serde(rename = "Costs / Q1")]
+        costs_q1: f64,
+        #[serde(rename = "Notes")]
+        notes: f64,
+    }
+
+    // banner row with merged group titles, then subheaders
+    let mut range = Range::new((0, 0), (3, 4));
+    range.set_value((0, 1), String("Sales".to_string()));
+    range.set_value((0, 3), String("Costs".to_string()));
+    for (col, header) in ["Region", "Q1", "Q2", "Q1", "Notes"]
+        .into_iter()
+        .enumerate()
+    {
+        range.set_value((1, col as u32), String(header.to_string()));
+    }
+    for (row, region) in [(2, "North"), (3, "South")] {
+        range.set_value((row, 0), String(region.to_string()));
+        for col in 1..5 {
+            range.set_value((row, col), Float((row * 10 + col) as f64));
+        }
+    }
+    let merged = [Dimensions::new((0, 1), (0, 2))];
+
+    let records = RangeDeserializerBuilder::new()
+        .with_header_rows(&[0, 1])
+        .unmerge_with(&merged)
+        .from_range(&range)
+        .unwrap()
+        .collect::<Result<Vec<Record>, _>>()
+        .unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Record {
+                region: "North".to_string(),
+                sales_q1: 21.,
+                sales_q2: 22.,
+                costs_q1: 23.,
+                notes: 24.
+            },
+            Record {
+                region: "South".to_string(),
+                sales_q1: 31.,
+                sales_q2: 32.,
+                costs_q1: 33.,
+                notes: 34.
+            },
+        ]
+    );
+
+    // selected composite headers
+    let mut iter = RangeDeserializerBuilder::with_headers(&["Costs / Q1", "Region"])
+        .with_header_rows(&[0, 1])
+        .unmerge_with(&merged)
+        .from_range(&range)
+        .unwrap();
+    let (costs, region): (f64, std::string::String) = iter.next().unwrap().unwrap();
+    assert_eq!((costs, region.as_str()), (23., "North"));
+
+    // without the merged regions, only the first cell of a group gets its title
+    let mut iter = RangeDeserializerBuilder::new()
+        .with_header_rows(&[0, 1])
+        .from_range::<_, HashMap<std::string::String, Data>>(&range)
+        .unwrap();
+    let mut headers = iter
+        .next()
+        .unwrap()
+        .unwrap()
+        .into_keys()
+        .collect::<Vec<_>>();
+    headers.sort();
+    assert_eq!(
+        headers,
+        ["Costs / Q1", "Notes", "Q2", "Region", "Sales / Q1"]
+    );
+
+    // not enough rows for the headers
+    let mut iter = RangeDeserializerBuilder::new()
+        .with_header_rows(&[0, 5])
+        .from_range::<_, Vec<Data>>(&range)
+        .unwrap();
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_deserialize_skip_empty_rows() {
+    use calamine::RangeDeserializerBuilder;
+    use serde_derive::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        label: std::string::String,
+        value: f64,
+    }
+
+    let mut range = Range::new((0, 0), (5, 1));
+    range.set_value((0, 0), String("label".to_string()));
+    range.set_value((0, 1), String("value".to_string()));
+    range.set_value((2, 0), String("a".to_string()));
+    range.set_value((2, 1), Float(1.));
+    range.set_value((5, 0), String("b".to_string()));
+    range.set_value((5, 1), Float(2.));
+
+    let records = RangeDeserializerBuilder::new()
+        .skip_empty_rows(true)
+        .from_range(&range)
+        .unwrap()
+        .collect::<Result<Vec<Record>, _>>()
+        .unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Record {
+                label: "a".to_string(),
+                value: 1.
+            },
+            Record {
+                label: "b".to_string(),
+                value: 2.
+            },
+        ]
+    );
+
+    // by default, blank rows are still deserialized (and fail here)
+    let records = RangeDeserializerBuilder::new()
+        .from_range(&range)
+        .unwrap()
+        .collect::<Result<Vec<Record>, _>>();
+    assert!(records.is_err());
+}
+
+#[test]
+fn test_deserialize_row_indexes() {
+    use calamine::{DeError, RangeDeserializerBuilder};
+
+    let mut range = Range::new((2, 1), (6, 1));
+    range.set_value((2, 1), String("value".to_string()));
+    range.set_value((3, 1), Float(1.));
+    range.set_value((5, 1), String("oops".to_string()));
+    range.set_value((6, 1), Float(3.));
+
+    let mut iter = RangeDeserializerBuilder::new()
+        .skip_empty_rows(true)
+        .from_range::<_, (f64,)>(&range)
+        .unwrap();
+    assert_eq!(iter.size_hint(), (0, Some(4)));
+    let (row, record) = iter.next_with_row().unwrap();
+    assert_eq!((row, record.unwrap()), (3, (1.,)));
+    let (row, record) = iter.next_with_row().unwrap();
+    assert_eq!(row, 5);
+    assert!(matches!(record, Err(DeError::Custom(_))));
+    let (row, record) = iter.next_with_row().unwrap();
+    assert_eq!((row, record.unwrap()), (6, (3.,)));
+    assert!(iter.next_with_row().is_none());
+
+    // errors report the cell position
+    let mut range = Range::new((1, 0), (2, 0));
+    range.set_value((1, 0), String("value".to_string()));
+    range.set_value((2, 0), Error(Div0));
+    let mut iter = RangeDeserializerBuilder::new()
+        .from_range::<_, (f64,)>(&range)
+        .unwrap();
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(DeError::CellError {
+            err: Div0,
+            pos: (2, 0)
+        })
+    );
+}
+
+#[test]
+fn test_deserialize_option_whitespace() {
+    use calamine::RangeDeserializerBuilder;
+    use serde_derive::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        value: Option<f64>,
+    }
+
+    let mut range = Range::new((0, 0), (3, 0));
+    range.set_value((0, 0), String("value".to_string()));
+    range.set_value((1, 0), Float(1.5));
+    range.set_value((3, 0), String("   ".to_string()));
+
+    // empty cells are always None
+    let mut iter = RangeDeserializerBuilder::new()
+        .from_range::<_, Record>(&range)
+        .unwrap();
+    assert_eq!(iter.next().unwrap(), Ok(Record { value: Some(1.5) }));
+    assert_eq!(iter.next().unwrap(), Ok(Record { value: None }));
+    // whitespaces are not empty by default
+    assert!(iter.next().unwrap().is_err());
+
+    for records in [
+        RangeDeserializerBuilder::new()
+            .whitespace_as_empty(true)
+            .from_range::<_, Record>(&range)
+            .unwrap()
+            .collect::<Vec<_>>(),
+        RangeDeserializerBuilder::new()
+            .has_headers(false)
+            .whitespace_as_empty(true)
+            .from_range::<_, (Option<f64>,)>(&range)
+            .unwrap()
+            .skip(1)
+            .map(|r| r.map(|(value,)| Record { value }))
+            .collect(),
+    ] {
+        assert_eq!(
+            records,
+            vec![
+                Ok(Record { value: Some(1.5) }),
+                Ok(Record { value: None }),
+                Ok(Record { value: None }),
+            ]
+        );
+    }
+
+    // blank rows are empty rows too
+    let values = RangeDeserializerBuilder::new()
+        .whitespace_as_empty(true)
+        .skip_empty_rows(true)
+        .from_range::<_, Record>(&range)
+        .unwrap()
+        .count();
+    assert_eq!(values, 1);
+}
+
+#[test]
+fn test_deserialize_unmerge() {
+    use calamine::RangeDeserializerBuilder;
+
+    let mut xlsx: Xlsx<_> = wb("merged_header.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    let merged = xlsx.worksheet_merge_cells("Sheet1").unwrap().unwrap();
+
+    let records = RangeDeserializerBuilder::new()
+        .unmerge_with(&merged)
+        .from_range(&range)
+        .unwrap()
+        .collect::<Result<Vec<(std::string::String, f64, f64)>, _>>()
+        .unwrap();
+    assert_eq!(
+        records,
+        [
+            ("red".to_string(), 1., 2.),
+            ("blue".to_string(), 3., 4.),
+            ("blue".to_string(), 5., 6.)
+        ]
+    );
+}
+
+#[test]
+fn test_deserialize_flatten() {
+    use calamine::RangeDeserializerBuilder;
+    use serde_derive::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Measure {
+        unit: std::string::String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        label: std::string::String,
+        #[serde(flatten)]
+        measure: Measure,
+        #[serde(flatten)]
+        extra: HashMap<std::string::String, Data>,
+    }
+
+    let mut range = Range::new((0, 0), (2, 3));
+    for (col, header) in ["label", "value", "unit", "comment"]
+        .into_iter()
+        .enumerate()
+    {
+        range.set_value((0, col as u32), String(header.to_string()));
+    }
+    range.set_value((1, 0), String("celsius".to_string()));
+    range.set_value((1, 1), Float(22.5));
+    range.set_value((1, 2), String("C".to_string()));
+    range.set_value((1, 3), String("room".to_string()));
+    range.set_value((2, 0), String("fahrenheit".to_string()));
+    range.set_value((2, 1), Int(72));
+    range.set_value((2, 2), String("F".to_string()));
+
+    let records = RangeDeserializerBuilder::new()
+        .from_range(&range)
+        .unwrap()
+        .collect::<Result<Vec<Record>, _>>()
+        .unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Record {
+                label: "celsius".to_string(),
+                measure: Measure {
+                    unit: "C".to_string()
+                },
+                extra: HashMap::from([
+                    ("value".to_string(), Float(22.5)),
+                    ("comment".to_string(), String("room".to_string())),
+                ]),
+            },
+            Record {
+                label: "fahrenheit".to_string(),
+                measure: Measure {
+                    unit: "F".to_string()
+                },
+                extra: HashMap::from([("value".to_string(), Int(72))]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_range_into_columns() {
+    let mut xlsx: Xlsx<_> = wb("temperature.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+
+    let columns = range.into_columns::<f64>(&["value"]).unwrap();
+    assert_eq!(columns.len(), 1);
+    assert_eq!(columns["value"], vec![22.2222, 72.0]);
+
+    let labels = range
+        .into_columns::<std::string::String>(&["label"])
+        .unwrap();
+    assert_eq!(labels["label"], vec!["celsius", "fahrenheit"]);
+
+    assert_eq!(
+        range.into_columns::<f64>(&["missing"]).unwrap_err(),
+        calamine::DeError::HeaderNotFound("missing".to_string())
+    );
+}
+
+#[test]
+fn test_cell_formula() {
+    let mut excel: Xlsx<_> = wb("issue_391.xlsx");
+    assert_eq!(
+        excel.cell_formula("Sheet1", 1, 0).unwrap().as_deref(),
+        Some("A1+1")
+    );
+    // shared formula
+    assert_eq!(
+        excel.cell_formula("Sheet1", 6, 0).unwrap().as_deref(),
+        Some("A6+1")
+    );
+    assert_eq!(excel.cell_formula("Sheet1", 0, 0).unwrap(), None);
+    assert_eq!(excel.cell_formula("Sheet1", 100, 100).unwrap(), None);
+    assert!(excel.cell_formula("Missing", 0, 0).is_err());
+}
+
+#[test]
+fn test_worksheet_outline_levels() {
+    let mut excel: Xlsx<_> = wb("outline_levels.xlsx");
+    let levels = excel.worksheet_outline_levels("Sheet1").unwrap();
+    assert_eq!(
+        levels,
+        OutlineLevels {
+            rows: [(1, 1), (2, 2), (3, 2), (4, 1)].into_iter().collect(),
+            columns: [(1, 1), (2, 1)].into_iter().collect(),
+        }
+    );
+    assert_eq!(levels.row_level(0), 0);
+    assert_eq!(levels.column_level(3), 0);
+}
+
+#[test]
+fn test_range_guess_header_row() {
+    let s = |v: &str| String(v.to_string());
+
+    // title above the header
+    let mut range = Range::new((2, 1), (5, 3));
+    range.set_value((2, 1), s("Quarterly report"));
+    range.set_value((3, 1), s("name"));
+    range.set_value((3, 2), s("amount"));
+    range.set_value((3, 3), s("date"));
+    for row in 4..=5 {
+        range.set_value((row, 1), s("item"));
+        range.set_value((row, 2), Float(1.5));
+        range.set_value((row, 3), Int(45000));
+    }
+    assert_eq!(range.guess_header_row(), Some(1));
+
+    // only strings, the first complete row wins
+    let mut range = Range::new((0, 0), (2, 1));
+    for row in 0..=2 {
+        range.set_value((row, 0), s(&format!("a{row}")));
+        range.set_value((row, 1), s(&format!("b{row}")));
+    }
+    assert_eq!(range.guess_header_row(), Some(0));
+
+    // repeated values are less likely headers
+    let mut range = Range::new((0, 0), (1, 1));
+    range.set_value((0, 0), s("x"));
+    range.set_value((0, 1), s("x"));
+    range.set_value((1, 0), s("x"));
+    range.set_value((1, 1), s("y"));
+    assert_eq!(range.guess_header_row(), Some(1));
+
+    // no strings
+    let mut range = Range::new((0, 0), (1, 0));
+    range.set_value((0, 0), Float(1.));
+    assert_eq!(range.guess_header_row(), None);
+    assert_eq!(Range::<Data>::empty().guess_header_row(), None);
+}
+
+#[test]
+fn flat_ods() {
+    let mut ods: Ods<_> = wb("flat.fods");
+    assert_eq!(
+        ods.sheets_metadata(),
+        &[
+            Sheet {
+                name: "Sheet1".to_string(),
+                typ: SheetType::WorkSheet,
+                visible: SheetVisible::Visible
+            },
+            Sheet {
+                name: "Hidden".to_string(),
+                typ: SheetType::WorkSheet,
+                visible: SheetVisible::Hidden
+            },
+        ]
+    );
+    assert_eq!(
+        ods.defined_names(),
+        &[("Values".to_string(), "$Sheet1.$B$2:.$B$3".to_string())]
+    );
+
+    let range = ods.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [
+                String("label".to_string()),
+                String("value".to_string()),
+                String("flag".to_string())
+            ],
+            [String("a".to_string()), Float(1.5), Bool(true)],
+            [String("b".to_string()), Float(3.), Bool(false)],
+        ]
+    );
+    let formula = ods.worksheet_formula("Sheet1").unwrap();
+    assert_eq!(formula.get_value((2, 1)).unwrap(), "of:=[.B2]*2");
+
+    // detected by extension or by content
+    let path = format!("{}/tests/flat.fods", env!("CARGO_MANIFEST_DIR"));
+    assert!(matches!(
+        open_workbook_auto(&path).unwrap(),
+        calamine::Sheets::Ods(_)
+    ));
+    let bytes = std::fs::read(&path).unwrap();
+    assert!(matches!(
+        open_workbook_auto_from_bytes(&bytes).unwrap(),
+        calamine::Sheets::Ods(_)
+    ));
+
+    // other xml documents are rejected
+    let res = open_workbook_from_slice::<Ods<_>>(b"<?xml version=\"1.0\"?><root/>");
+    assert!(matches!(res, Err(calamine::OdsError::Mismatch { .. })));
+
+    // anything else is not read as xml
+    let res = open_workbook_from_slice::<Ods<_>>(b"not a spreadsheet");
+    assert!(matches!(res, Err(calamine::OdsError::Zip(_))));
+}
+
+#[test]
+fn flat_ods_pretty_printed() {
+    let mut ods: Ods<_> = wb("flat_pretty.fods");
+    let range = ods.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [String("label".to_string()), Float(1.5)],
+            [String("first line\nsecond line".to_string()), Empty],
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn csv_reader() {
+    use calamine::Csv;
+
+    let mut csv: Csv<_> = wb("types.csv");
+    assert_eq!(csv.sheet_names(), vec!["Sheet1"]);
+    let range = csv.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [
+                String("name".to_string()),
+                String("count".to_string()),
+                String("ratio".to_string()),
+                String("flag".to_string()),
+                String("empty".to_string())
+            ],
+            [
+                String("Smith, John".to_string()),
+                Int(12),
+                Float(0.5),
+                Bool(true),
+                Empty
+            ],
+            [
+                String("line\nbreak".to_string()),
+                Int(-3),
+                Float(1000.),
+                Bool(false),
+                Empty
+            ],
+            [
+                String("nan".to_string()),
+                Int(7),
+                Empty,
+                String("x".to_string()),
+                Empty
+            ],
+        ]
+    );
+    assert!(csv.worksheet_formula("Sheet1").unwrap().is_empty());
+    assert!(matches!(
+        csv.worksheet_range("Sheet2"),
+        Err(calamine::CsvError::WorksheetNotFound(_))
+    ));
+
+    let range = csv
+        .with_header_row(HeaderRow::Row(2))
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((2, 0)));
+
+    let path = format!("{}/tests/types.csv", env!("CARGO_MANIFEST_DIR"));
+    assert!(matches!(
+        open_workbook_auto(path).unwrap(),
+        calamine::Sheets::Csv(_)
+    ));
+}
+
+#[test]
+fn test_relationship_targets() {
+    let mut excel: Xlsx<_> = wb("relationship_targets.xlsx");
+    for (i, name) in excel.sheet_names().iter().enumerate() {
+        let range = excel.worksheet_range(name).unwrap();
+        range_eq!(range, [[Float((i + 1) as f64)]]);
+    }
+    assert_eq!(excel.sheet_names().len(), 4);
+}
+
+#[test]
+fn test_wrong_dimension() {
+    let mut excel: Xlsx<_> = wb("wrong_dimension.xlsx");
+    for name in ["UnderReported", "Reversed", "Invalid", "Missing"] {
+        let range = excel.worksheet_range(name).unwrap();
+        assert_eq!(range.start(), Some((1, 1)), "{name}");
+        assert_eq!(range.end(), Some((4, 3)), "{name}");
+        assert_eq!(range.get_value((4, 3)), Some(&Float(2.)), "{name}");
+    }
+}
+
+#[test]
+fn test_archive_comment() {
+    let excel: Xlsx<_> = wb("archive_comment.xlsx");
+    assert_eq!(excel.archive_comment(), Some("generated by pipeline v2"));
+
+    let excel: Xlsx<_> = wb("issues.xlsx");
+    assert_eq!(excel.archive_comment(), None);
+}
+
+#[test]
+fn test_calc_chain() {
+    let mut excel: Xlsx<_> = wb("calc_chain.xlsx");
+    let calc = |row, col| ("Calc".to_string(), row, col);
+    assert_eq!(
+        excel.calc_chain().unwrap(),
+        [
+            calc(0, 1),
+            calc(1, 0),
+            calc(1, 1),
+            ("Data".to_string(), 0, 0)
+        ]
+    );
+
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+    assert_eq!(excel.calc_chain().unwrap()[0], ("Sheet1".to_string(), 1, 0));
+
+    // no calculation chain
+    let mut excel: Xlsx<_> = wb("archive_comment.xlsx");
+    assert_eq!(excel.calc_chain().unwrap(), []);
+}
+
+#[test]
+fn test_worksheet_row_spans() {
+    let mut excel: Xlsx<_> = wb("row_spans.xlsx");
+    assert_eq!(
+        excel.worksheet_row_spans("Sheet1").unwrap(),
+        [(0, 0, 2), (2, 1, 5), (4, 3, 3)]
+    );
+    assert!(excel.worksheet_row_spans("Missing").is_err());
+}
+
+#[test]
+fn test_range_fill_down() {
+    let s = |v: &str| String(v.to_string());
+    let mut range = Range::new((1, 1), (5, 2));
+    range.set_value((2, 1), s("fruit"));
+    range.set_value((2, 2), Int(1));
+    range.set_value((3, 2), Int(2));
+    range.set_value((4, 1), s("vegetable"));
+    range.set_value((4, 2), Int(3));
+    range.set_value((5, 2), Int(4));
+
+    range.fill_down(&[0, 10]);
+    range_eq!(
+        range,
+        [
+            [Empty, Empty],
+            [s("fruit"), Int(1)],
+            [s("fruit"), Int(2)],
+            [s("vegetable"), Int(3)],
+            [s("vegetable"), Int(4)]
+        ]
+    );
+}
+
+#[test]
+fn test_range_drop_empty_rows_columns() {
+    let mut range = Range::new((1, 1), (4, 4));
+    range.set_value((1, 1), Int(1));
+    range.set_value((1, 3), Int(2));
+    range.set_value((3, 1), Int(3));
+    range.set_value((3, 3), Int(4));
+    range.set_value((3, 4), Int(5));
+
+    // interior blank column 2 and trailing blank row 4
+    range_eq!(
+        range.drop_empty_columns(),
+        [
+            [Int(1), Int(2), Empty],
+            [Empty, Empty, Empty],
+            [Int(3), Int(4), Int(5)],
+            [Empty, Empty, Empty]
+        ]
+    );
+    assert_eq!(range.drop_empty_columns().end(), Some((4, 3)));
+
+    let rows = range.drop_empty_rows();
+    assert_eq!(rows.start(), Some((1, 1)));
+    assert_eq!(rows.end(), Some((2, 4)));
+    range_eq!(
+        rows,
+        [
+            [Int(1), Empty, Int(2), Empty],
+            [Int(3), Empty, Int(4), Int(5)]
+        ]
+    );
+
+    range_eq!(
+        range.drop_empty_rows().drop_empty_columns(),
+        [[Int(1), Int(2), Empty], [Int(3), Int(4), Int(5)]]
+    );
+
+    let empty = Range::<Data>::new((0, 0), (2, 2));
+    assert!(empty.drop_empty_rows().is_empty());
+    assert!(empty.drop_empty_columns().is_empty());
+}
+
+#[test]
+fn test_stop_at_empty_row() {
+    let mut excel: Xlsx<_> = wb("stop_at_empty_row.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.end(), Some((7, 2)));
+
+    let range = excel
+        .with_stop_at_empty_row(true)
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((1, 0)));
+    assert_eq!(range.end(), Some((4, 1)));
+    assert_eq!(range.get_value((4, 1)), Some(&Float(5.)));
+
+    // the header row may be below empty rows
+    let range = excel
+        .with_header_row(HeaderRow::Row(0))
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((4, 1)));
+
+    // no padding over the ignored rows
+    let range = excel
+        .with_header_row(HeaderRow::FirstNonEmptyRow)
+        .with_declared_dimensions(true)
+        .worksheet_range("Sheet1")
+        .unwrap();
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((4, 2)));
+}
+
+#[test]
+fn test_shared_string() {
+    let excel: Xlsx<_> = wb("issues.xlsx");
+    assert_eq!(excel.shared_string_count(), 12);
+    assert_eq!(excel.shared_string(0), Some("a"));
+    assert_eq!(excel.shared_string(4), Some("&"));
+    assert_eq!(excel.shared_string(12), None);
+}
+
+#[test]
+fn test_chartsheet_series() {
+    let mut xlsx: Xlsx<_> = wb("any_sheets.xlsx");
+    let series = xlsx.chartsheet_series("Chart").unwrap();
+    assert_eq!(
+        series,
+        vec![
+            ChartSeries {
+                name: None,
+                categories: None,
+                values: Some("Visible!$A$1:$A$3".to_string()),
+            },
+            ChartSeries {
+                name: None,
+                categories: None,
+                values: Some("Visible!$B$1:$B$3".to_string()),
+            },
+        ]
+    );
+    assert!(matches!(
+        xlsx.chartsheet_series("Visible"),
+        Err(XlsxError::NotAChartsheet(_))
+    ));
+}
+
+#[test]
+fn test_spill_errors() {
+    let mut xlsx: Xlsx<_> = wb("spill_errors.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    range_eq!(range, [[Float(1.), Error(Spill)], [Float(2.), Error(Calc)]]);
+}
+
+#[test]
+fn test_spill_errors_xlsb() {
+    let mut xlsb: Xlsb<_> = wb("spill_errors.xlsb");
+    let range = xlsb.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.get((0, 0)), Some(&Error(Spill)));
+    assert_eq!(range.get((0, 1)), Some(&Error(Calc)));
+
+    let formula = xlsb.worksheet_formula("Sheet1").unwrap();
+    assert_eq!(formula.start(), Some((1, 0)));
+    range_eq!(
+        formula,
+        [[
+            "#SPILL!".to_string(),
+            "#CALC!".to_string(),
+            "{#SPILL!,#CALC!}".to_string()
+        ]]
+    );
+}
+
+#[test]
+fn test_worksheet_range_f64() {
+    let mut xlsx: Xlsx<_> = wb("temperature.xlsx");
+    let range = xlsx.worksheet_range_f64("Sheet1").unwrap();
+    assert_eq!(range.get_size(), (3, 2));
+    assert!(range[(0, 1)].is_nan());
+    assert!(range[(1, 0)].is_nan());
+    assert_eq!(range[(1, 1)], 22.2222);
+    assert_eq!(range[(2, 1)], 72.0);
+    // the usual `Range` methods are available
+    assert_eq!(range.get_value((2, 1)), Some(&72.0));
+    assert_eq!(range.get_value((9, 9)), None);
+    assert_eq!(
+        range
+            .rows()
+            .map(|r| r.iter().filter(|v| !v.is_nan()).count())
+            .collect::<Vec<_>>(),
+        [0, 1, 1]
+    );
+
+    let mut xlsx: Xlsx<_> = wb("date.xlsx");
+    let range = xlsx.worksheet_range_f64("Sheet1").unwrap();
+    assert_eq!(range[(0, 0)], 44197.0);
+}
+
+#[test]
+fn test_worksheet_range_f64_strict() {
+    let mut xlsx: Xlsx<_> = wb("temperature.xlsx");
+    assert_eq!(
+        xlsx.worksheet_range_f64_strict("Sheet1").unwrap_err(),
+        XlsxError::NotANumber { row: 0, col: 0 }
+    );
+
+    // only the header row is text
+    let mut xlsx: Xlsx<_> = wb("1000_rows.xlsx");
+    xlsx.with_header_row(HeaderRow::Row(1));
+    let range = xlsx.worksheet_range_f64_strict("Sheet1").unwrap();
+    let lax = xlsx.worksheet_range_f64("Sheet1").unwrap();
+    assert_eq!(range.start(), lax.start());
+    assert_eq!(range.get_size(), lax.get_size());
+    assert!(range
+        .rows()
+        .flatten()
+        .zip(lax.rows().flatten())
+        .all(|(a, b)| a == b || (a.is_nan() && b.is_nan())));
+}
+
+#[test]
+fn test_text_format() {
+    let mut xlsx: Xlsx<_> = wb("text_format.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [[
+            String("007".to_string()),
+            String("0012".to_string()),
+            Float(7.)
+        ]]
+    );
+}
+
+#[test]
+fn test_xlsb_extern_sheets() {
+    let xlsb: Xlsb<_> = wb("issues.xlsb");
+    assert_eq!(xlsb.extern_sheet_name(0), Some("datatypes"));
+    assert_eq!(xlsb.extern_sheet_name(1), Some("Sheet1"));
+    assert_eq!(xlsb.extern_sheet_name(2), None);
+    assert_eq!(xlsb.sheet_index("datatypes"), Some(0));
+    assert_eq!(xlsb.sheet_index("spc_chrs"), Some(5));
+
+    let xlsb: Xlsb<_> = wb("issue_182.xlsb");
+    assert_eq!(xlsb.extern_sheet_name(0), Some("#InvalidWorkSheet"));
+}
+
+#[test]
+fn test_resolve_circular_defined_names() {
+    let xlsx: Xlsx<_> = wb("circular_names.xlsx");
+    let data = vec![(
+        "Sheet1".to_string(),
+        Dimensions {
+            start: (0, 0),
+            end: (1, 1),
+        },
+    )];
+    assert_eq!(xlsx.resolve_defined_name("Data"), Some(data.clone()));
+    assert_eq!(xlsx.resolve_defined_name("Alias"), Some(data));
+    assert_eq!(xlsx.resolve_defined_name("Ping"), None);
+    assert_eq!(xlsx.resolve_defined_name("Pong"), None);
+    assert_eq!(xlsx.resolve_defined_name("Self"), None);
+}
+
+#[test]
+fn test_many_merge_cells() {
+    let mut xlsx: Xlsx<_> = wb("many_merge_cells.xlsx");
+    let merge_cells = xlsx.worksheet_merge_cells("Sheet1").unwrap().unwrap();
+    assert_eq!(merge_cells.len(), 5000);
+    assert_eq!(merge_cells[4999], Dimensions::new((9998, 0), (9999, 1)));
+
+    xlsx.load_merged_regions().unwrap();
+    assert_eq!(xlsx.merged_regions().len(), 5000);
+}
+
+#[test]
+fn test_merge_cells_wrong_count() {
+    // `count` says 1 but there are 3 merges
+    let mut xlsx: Xlsx<_> = wb("merge_cells_wrong_count.xlsx");
+    let merge_cells = xlsx.worksheet_merge_cells("Sheet1").unwrap().unwrap();
+    assert_eq!(
+        merge_cells,
+        [
+            Dimensions::new((0, 0), (0, 1)),
+            Dimensions::new((1, 0), (1, 1)),
+            Dimensions::new((2, 0), (2, 1)),
+        ]
+    );
+
+    xlsx.load_merged_regions().unwrap();
+    assert_eq!(xlsx.merged_regions().len(), 3);
+}
+
+#[test]
+fn test_xlsx_all_dimensions() {
+    let mut xlsx: Xlsx<_> = wb("issues.xlsx");
+    let dimensions = xlsx.all_dimensions().unwrap();
+    assert_eq!(dimensions.len(), xlsx.sheet_names().len());
+    for (name, dim) in &dimensions {
+        assert_eq!(*dim, xlsx.worksheet_dimensions(name).unwrap());
+    }
+    assert_eq!(dimensions[0].0, xlsx.sheet_names()[0]);
+
+    let mut xlsx: Xlsx<_> = wb("declared_dimensions.xlsx");
+    assert_eq!(
+        xlsx.all_dimensions().unwrap(),
+        [("Sheet1".to_string(), Dimensions::new((0, 0), (4, 5)))]
+    );
+}
+
+#[test]
+fn test_deserialize_duplicate_headers() {
+    use calamine::{DeError, DuplicateHeader, RangeDeserializerBuilder};
+    use std::collections::HashMap;
+
+    let mut range = Range::new((0, 0), (1, 2));
+    for (col, header) in ["label", "value", "value"].into_iter().enumerate() {
+        range.set_value((0, col as u32), String(header.to_string()));
+    }
+    range.set_value((1, 0), String("a".to_string()));
+    range.set_value((1, 1), Float(1.));
+    range.set_value((1, 2), Float(2.));
+
+    let first = |policy| {
+        let mut iter = RangeDeserializerBuilder::new()
+            .on_duplicate_header(policy)
+            .from_range::<_, HashMap<std::string::String, Data>>(&range)?;
+        iter.next().unwrap()
+    };
+    assert_eq!(first(DuplicateHeader::First).unwrap()["value"], Float(1.));
+    assert_eq!(first(DuplicateHeader::Last).unwrap()["value"], Float(2.));
+    assert_eq!(
+        first(DuplicateHeader::Error),
+        Err(DeError::DuplicateHeader("value".to_string()))
+    );
+
+    let custom = |policy| {
+        let mut iter = RangeDeserializerBuilder::with_headers(&["value", "label"])
+            .on_duplicate_header(policy)
+            .from_range::<_, (f64, std::string::String)>(&range)?;
+        iter.next().unwrap()
+    };
+    assert_eq!(custom(DuplicateHeader::First), Ok((1., "a".to_string())));
+    assert_eq!(custom(DuplicateHeader::Last), Ok((2., "a".to_string())));
+    assert_eq!(
+        custom(DuplicateHeader::Error),
+        Err(DeError::DuplicateHeader("value".to_string()))
+    );
+}
+
+#[test]
+fn test_content_type() {
+    let xlsx: Xlsx<_> = wb("picture.xlsx");
+    assert_eq!(
+        xlsx.content_type("xl/workbook.xml"),
+        Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml")
+    );
+    assert_eq!(
+        xlsx.content_type("/XL/Worksheets/Sheet1.xml"),
+        Some("application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml")
+    );
+    assert_eq!(xlsx.content_type("xl/media/image1.jpg"), Some("image/jpeg"));
+    assert_eq!(xlsx.content_type("xl/media/image3.PNG"), Some("image/png"));
+    assert_eq!(xlsx.content_type("xl/custom.xml"), Some("application/xml"));
+    assert_eq!(xlsx.content_type("xl/media/movie.mp4"), None);
+    assert_eq!(xlsx.content_type("xl/media"), None);
+
+    // an invalid [Content_Types].xml doesn't prevent reading the workbook
+    let mut xlsx: Xlsx<_> = wb("bad_content_types.xlsx");
+    assert_eq!(xlsx.content_type("xl/workbook.xml"), None);
+    range_eq!(xlsx.worksheet_range("Sheet1").unwrap(), [[Float(1.)]]);
+}
+
+#[test]
+fn test_range_find() {
+    let mut xlsx: Xlsx<_> = wb("temperature.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.find(&String("celsius".to_string())), Some((1, 0)));
+    assert_eq!(range.find(&Float(72.)), Some((2, 1)));
+    assert_eq!(range.find(&String("kelvin".to_string())), None);
+    assert_eq!(range.find_all(&Float(72.)), vec![(2, 1)]);
+
+    let mut range = Range::new((2, 3), (4, 4));
+    range.set_value((2, 4), Int(1));
+    range.set_value((4, 3), Int(1));
+    assert_eq!(range.find(&Int(1)), Some((2, 4)));
+    assert_eq!(range.find_all(&Int(1)), vec![(2, 4), (4, 3)]);
+    assert_eq!(range.find_all(&Int(2)), vec![]);
+    assert_eq!(Range::<Data>::empty().find(&Empty), None);
+}
+
+#[test]
+fn test_range_column_index_of() {
+    let mut xlsx: Xlsx<_> = wb("temperature.xlsx");
+    let range = xlsx.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.column_index_of("label"), Some(0));
+    assert_eq!(range.column_index_of("value"), Some(1));
+    assert_eq!(range.column_index_of("Value"), None);
+    assert_eq!(range.column_index_of_ignore_case("VALUE"), Some(1));
+    assert_eq!(range.column_index_of("celsius"), None);
+    assert_eq!(Range::<Data>::empty().column_index_of("label"), None);
+}
+
+#[test]
+fn test_xlt_template() {
+    let path = format!("{}/tests/template.xlt", env!("CARGO_MANIFEST_DIR"));
+    let mut workbook = open_workbook_auto(&path).unwrap();
+    assert!(matches!(workbook, calamine::Sheets::Xls(_)));
+    let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+    assert_eq!(
+        range.get_value((0, 0)),
+        Some(&DateTime(ExcelDateTime::new(
+            44197.0,
+            ExcelDateTimeType::DateTime,
+            false
+        )))
+    );
+
+    let mut xls: Xls<_> = wb("template.xlt");
+    let template = xls.worksheet_range_at(0).unwrap().unwrap();
+    let mut original: Xls<_> = wb("date.xls");
+    let original = original.worksheet_range_at(0).unwrap().unwrap();
+    assert!(template.content_eq(&original));
+
+    // the extension doesn't match the content
+    let path = format!("{}/tests/xlsx_saved_as.xlt", env!("CARGO_MANIFEST_DIR"));
+    let workbook = open_workbook_auto(&path).unwrap();
+    assert!(matches!(workbook, calamine::Sheets::Xlsx(_)));
+}
+
+#[test]
+fn test_worksheet_range_with_merge_info() {
+    let mut xlsx: Xlsx<_> = wb("merge_cells.xlsx");
+    let range = xlsx.worksheet_range_with_merge_info("Sheet1").unwrap();
+    let values = xlsx.worksheet_range("Sheet1").unwrap();
+    // merged regions are clipped to the range
+    assert_eq!(values.end(), Some((1, 2)));
+    assert_eq!(range.start(), values.start());
+    assert_eq!(range.end(), values.end());
+    for (row, col, (value, _)) in range.cells() {
+        assert_eq!(value, &values[(row, col)]);
+    }
+
+    let header = Dimensions::new((0, 0), (0, 1));
+    let block = Dimensions::new((1, 1), (3, 3));
+    assert_eq!(range[(0, 0)].1, MergeRole::Master(header));
+    assert_eq!(range[(0, 1)].1, MergeRole::Covered(header));
+    assert_eq!(range[(1, 1)].1, MergeRole::Master(block));
+    assert_eq!(range[(1, 2)].1, MergeRole::Covered(block));
+    assert_eq!(
+        range[(1, 0)].1,
+        MergeRole::Master(Dimensions::new((1, 0), (3, 0)))
+    );
+    assert_eq!(
+        range[(0, 2)],
+        (String("Not Merged".to_string()), MergeRole::None)
+    );
+    let standalone = range
+        .cells()
+        .filter(|(_, _, (_, role))| *role == MergeRole::None)
+        .count();
+    assert_eq!(standalone, 1);
+
+    // rows skipped by the header row are not brought back by the merged header
+    xlsx.with_header_row(HeaderRow::Row(1));
+    let range = xlsx.worksheet_range_with_merge_info("Sheet1").unwrap();
+    assert_eq!(range.start(), Some((1, 0)));
+    assert_eq!(range.end(), Some((1, 1)));
+    assert_eq!(range[(0, 1)].1, MergeRole::Master(block));
+}
+
+#[test]
+fn test_with_part_reader() {
+    use quick_xml::events::Event;
+
+    let mut xlsx: Xlsx<_> = wb("issues.xlsx");
+    let application = xlsx
+        .with_part_reader("/docProps/app.xml", |xml| {
+            let mut buf = Vec::new();
+            let mut in_application = false;
+            loop {
+                match xml.read_event_into(&mut buf)? {
+                    Event::Start(e) => in_application = e.local_name().as_ref() == b"Application",
+                    Event::Text(t) if in_application => {
+                        return Ok(Some(t.unescape()?.into_owned()))
+                    }
+                    Event::Eof => return Ok(None),
+                    _ => (),
+                }
+                buf.clear();
+            }
+        })
+        .unwrap();
+    assert_eq!(application.as_deref(), Some("Microsoft Excel"));
+
+    assert_eq!(
+        xlsx.with_part_reader("docProps/missing.xml", |_| Ok(())),
+        Err(XlsxError::FileNotFound("docProps/missing.xml".to_string()))
+    );
+}
+
+#[test]
+fn test_worksheet_visible_range() {
+    let mut excel: Xlsx<_> = wb("filtered_rows.xlsx");
+    let range = excel.worksheet_visible_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [String("Name".to_string()), String("Qty".to_string())],
+            [String("apple".to_string()), Float(1.)],
+            [String("plum".to_string()), Float(3.)],
+            [String("fig".to_string()), Float(5.)],
+        ]
+    );
+    assert_eq!(excel.worksheet_range("Sheet1").unwrap().height(), 6);
+
+    // no hidden rows
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+    let visible = excel.worksheet_visible_range("issue2").unwrap();
+    let range = excel.worksheet_range("issue2").unwrap();
+    assert_eq!(visible.start(), range.start());
+    assert!(visible.rows().eq(range.rows()));
+}
+
+#[test]
+fn test_theme_fonts() {
+    let excel: Xlsx<_> = wb("any_sheets.xlsx");
+    assert_eq!(
+        excel.theme_fonts(),
+        Some(("Calibri Light".to_string(), "Calibri".to_string()))
+    );
+
+    // no theme part
+    let excel: Xlsx<_> = wb("number_format_codes.xlsx");
+    assert_eq!(excel.theme_fonts(), None);
+
+    // an invalid theme doesn't prevent reading the workbook
+    let mut excel: Xlsx<_> = wb("bad_theme.xlsx");
+    assert_eq!(excel.theme_fonts(), None);
+    range_eq!(excel.worksheet_range("Sheet1").unwrap(), [[Float(1.)]]);
+}
+
+#[test]
+fn test_trim_strings() {
+    let mut excel: Xlsx<_> = wb("untrimmed_strings.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    assert_eq!(
+        range.get_value((0, 0)),
+        Some(&String("  name ".to_string()))
+    );
+    assert_eq!(
+        range.get_value((0, 1)),
+        Some(&String("\tvalue ".to_string()))
+    );
+
+    let range = excel
+        .with_trim_strings(true)
+        .worksheet_range("Sheet1")
+        .unwrap();
+    range_eq!(
+        range,
+        [
+            [
+                String("name".to_string()),
+                String("value".to_string()),
+                Empty
+            ],
+            [
+                String("plain".to_string()),
+                String("x".to_string()),
+                Float(1.)
+            ],
+        ]
+    );
+    // the shared string table is not modified
+    assert_eq!(excel.shared_string(0), Some("  name "));
+}
+
+#[test]
+fn test_data_connections() {
+    let mut excel: Xlsx<_> = wb("data_connections.xlsx");
+    let connection = |name: &str, typ, source: &str| calamine::DataConnection {
+        name: name.to_string(),
+        typ,
+        source: Some(source.to_string()),
+    };
+    assert_eq!(
+        excel.data_connections().unwrap(),
+        vec![
+            connection("Sales", 1, "SELECT * FROM sales"),
+            connection("Rates & fees", 4, "https://example.com/rates?a=1&b=2"),
+            connection("Import", 6, "C:\\data\\import.csv"),
+            connection("Model", 5, "Provider=MSOLAP;Data Source=Model"),
+        ]
+    );
+
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+    assert!(excel.data_connections().unwrap().is_empty());
+}
+
+#[test]
+fn test_custom_xml_parts() {
+    let mut excel: Xlsx<_> = wb("custom_xml.xlsx");
+    let parts = excel.custom_xml_parts().unwrap();
+    let names: Vec<_> = parts.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(names, ["customXml/item1.xml", "customXml/item2.xml"]);
+    let content = std::str::from_utf8(&parts[0].1).unwrap();
+    assert!(content.ends_with(
+        "<DataMashup xmlns=\"http://schemas.microsoft.com/DataMashup\">AAAAAA==</DataMashup>"
+    ));
+
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+    assert!(excel.custom_xml_parts().unwrap().is_empty());
+}
+
+#[test]
+fn test_truncated_xlsx() {
+    let path = format!("{}/tests/truncated.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let err = open_workbook::<Xlsx<_>, _>(&path).err();
+    assert_eq!(err, Some(XlsxError::Truncated));
+
+    // not a zip at all: left to the zip reader
+    let path = format!("{}/tests/date.xls", env!("CARGO_MANIFEST_DIR"));
+    let err = open_workbook::<Xlsx<_>, _>(&path).err();
+    assert!(matches!(err, Some(XlsxError::Zip(_))));
+}
+
+#[test]
+fn test_cells_reader_is_formula() {
+    let mut excel: Xlsx<_> = wb("untrimmed_strings.xlsx");
+    let mut reader = excel.worksheet_cells_reader("Sheet1").unwrap();
+    let mut cells = Vec::new();
+    while let Some(cell) = reader.next_cell().unwrap() {
+        cells.push((
+            cell.get_position(),
+            cell.get_value().is_string(),
+            reader.is_formula(),
+        ));
+    }
+    assert_eq!(
+        cells,
+        [
+            ((0, 0), true, false), // shared string
+            ((0, 1), true, false), // inline string
+            ((1, 0), true, false),
+            ((1, 1), true, true), // formula string result
+            ((1, 2), false, false),
+        ]
+    );
+}
+
+#[test]
+fn test_prefetch_sheet() {
+    let mut excel: Xlsx<_> = wb("merge_cells.xlsx");
+    let merges = excel.worksheet_merge_cells("Sheet1").unwrap().unwrap();
+    let dimensions = excel.worksheet_dimensions("Sheet1").unwrap();
+
+    let mut excel: Xlsx<_> = wb("merge_cells.xlsx");
+    excel.prefetch_sheet("Sheet1").unwrap();
+    excel.prefetch_sheet("Sheet1").unwrap();
+    assert_eq!(
+        excel.worksheet_merge_cells("Sheet1").unwrap().unwrap(),
+        merges
+    );
+    assert_eq!(excel.worksheet_dimensions("Sheet1").unwrap(), dimensions);
+    assert!(excel.worksheet_merge_cells("Missing").is_none());
+
+    let mut excel: Xlsx<_> = wb("any_sheets.xlsx");
+    assert!(matches!(
+        excel.prefetch_sheet("Chart"),
+        Err(XlsxError::NotAWorksheet(_))
+    ));
+    assert_eq!(excel.worksheet_merge_cells("Chart"), Some(Ok(Vec::new())));
+    assert_eq!(
+        excel.prefetch_sheet("Missing"),
+        Err(XlsxError::WorksheetNotFound("Missing".to_string()))
+    );
+}
+
+#[test]
+fn test_strict_ooxml() {
+    let mut excel: Xlsx<_> = wb("strict.xlsx");
+    assert_eq!(excel.sheet_names(), ["Data"]);
+    let range = excel.worksheet_range("Data").unwrap();
+    range_eq!(
+        range,
+        [
+            [
+                String("Item".to_string()),
+                String("Date".to_string()),
+                String("Total".to_string())
+            ],
+            [
+                String("apple".to_string()),
+                DateTime(ExcelDateTime::new(1., ExcelDateTimeType::DateTime, true)),
+                Float(2.5)
+            ],
+        ]
+    );
+    assert_eq!(
+        excel.worksheet_merge_cells("Data"),
+        Some(Ok(vec![Dimensions::new((0, 3), (0, 4))]))
+    );
+    excel.load_tables().unwrap();
+    let table = excel.table_by_name("Fruits").unwrap();
+    assert_eq!(table.columns(), ["Item", "Date", "Total"]);
+}
+
+#[test]
+fn test_xlsx_lazy_cell_parts() {
+    let open = |name: &str, options| {
+        let path = format!("{}/tests/{name}", env!("CARGO_MANIFEST_DIR"));
+        let reader = BufReader::new(File::open(path).unwrap());
+        Xlsx::new_with_options(reader, options).unwrap()
+    };
+    let mut options = XlsxOptions::default();
+    options.lazy_cell_parts = true;
+
+    let mut excel = open("issues.xlsx", options.clone());
+    assert_eq!(excel.shared_string_count(), 0);
+    let mut eager: Xlsx<_> = wb("issues.xlsx");
+    assert_eq!(excel.sheet_names(), eager.sheet_names());
+    let range = excel.worksheet_range("issue2").unwrap();
+    let expected = eager.worksheet_range("issue2").unwrap();
+    assert!(range.rows().eq(expected.rows()));
+    assert_eq!(excel.shared_string_count(), eager.shared_string_count());
+    // parts are only read once
+    excel.worksheet_range("issue2").unwrap();
+    assert_eq!(excel.shared_string_count(), eager.shared_string_count());
+
+    let mut excel = open("number_format_codes.xlsx", options.clone());
+    assert_eq!(excel.cell_format_string(1), None);
+    let cells: Vec<_> = excel.worksheet_cells_formatted("Sheet1").collect();
+    assert_eq!(
+        cells[1],
+        Ok((0, 1, Float(1234.5), Some("#,##0.00 \"EUR\"".to_string())))
+    );
+    assert_eq!(excel.cell_format_string(1), Some("#,##0.00 \"EUR\""));
+
+    let mut excel = open("number_format_codes.xlsx", options.clone());
+    excel.load_cell_parts().unwrap();
+    assert_eq!(excel.cell_format_string(1), Some("#,##0.00 \"EUR\""));
+
+    // a failed load can be retried without duplicating the shared strings
+    let mut excel = open("bad_styles.xlsx", options);
+    assert!(excel.load_cell_parts().is_err());
+    assert!(excel.load_cell_parts().is_err());
+    assert_eq!(excel.shared_string_count(), 2);
+}
+
+#[test]
+#[cfg(feature = "picture")]
+fn test_xlsx_lazy_pictures() {
+    let path = format!("{}/tests/picture.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut options = XlsxOptions::default();
+    options.lazy_pictures = true;
+    let mut excel =
+        Xlsx::new_with_options(BufReader::new(File::open(path).unwrap()), options).unwrap();
+    assert!(excel.pictures().is_none());
+    excel.load_pictures().unwrap();
+    let eager: Xlsx<_> = wb("picture.xlsx");
+    assert_eq!(excel.pictures(), eager.pictures());
+}
+
+#[test]
+fn test_xlsb_lazy_shared_strings() {
+    let path = format!("{}/tests/issues.xlsb", env!("CARGO_MANIFEST_DIR"));
+    let mut options = XlsbOptions::default();
+    options.lazy_shared_strings = true;
+    let mut lazy =
+        Xlsb::new_with_options(BufReader::new(File::open(path).unwrap()), options).unwrap();
+    let mut eager: Xlsb<_> = wb("issues.xlsb");
+    for name in eager.sheet_names() {
+        let expected = eager.worksheet_range(&name).unwrap();
+        let range = lazy.worksheet_range(&name).unwrap();
+        assert_eq!(range.start(), expected.start());
+        assert!(range.rows().eq(expected.rows()), "{name}");
+    }
+}
+
+#[test]
+fn test_xlsb_mem_area_formulas() {
+    let mut excel: Xlsb<_> = wb("mem_area.xlsb");
+    let name = excel.sheet_names()[0].clone();
+    let formulas = excel.worksheet_formula(&name).unwrap();
+    assert_eq!(formulas.start(), Some((1, 0)));
+    assert_eq!(
+        formulas.rows().collect::<Vec<_>>(),
+        [["SUM($A$1:$B$1)".to_string(), "A1".to_string()]]
+    );
+    let range = excel.worksheet_range(&name).unwrap();
+    range_eq!(range, [[Float(1.), Float(2.)], [Float(3.), Float(1.)]]);
+}
+
+#[test]
+fn test_content_hash_persisted() {
+    // the hash of a `Range<Data>` must not change between versions
+    let mut range = Range::new((0, 0), (1, 2));
+    range.set_value((0, 0), Int(1));
+    range.set_value((0, 1), Float(1.5));
+    range.set_value((0, 2), String("a".to_string()));
+    range.set_value((1, 0), Bool(true));
+    range.set_value((1, 1), Error(NA));
+    range.set_value((1, 2), DateTimeIso("2021-01-01".to_string()));
+    assert_eq!(range.content_hash(), 2291473852177422997);
+}